@@ -0,0 +1,198 @@
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+#[path = "src/common/layout.rs"]
+mod layout;
+
+use layout::{module_name, source_path, DEFAULT_YEAR};
+
+// Example inputs live in data/<year>/examples/NN.txt or data/<year>/examples/NN-<label>.txt
+fn parse_example_name(file_name: &str) -> Option<(u32, Option<String>)> {
+    let stem = file_name.strip_suffix(".txt")?;
+    let problem = stem.get(0..2)?.parse().ok()?;
+    match &stem[2..] {
+        "" => Some((problem, None)),
+        rest => {
+            let label = rest.strip_prefix('-')?;
+            label
+                .chars()
+                .all(char::is_alphanumeric)
+                .then(|| (problem, Some(label.to_string())))
+        }
+    }
+}
+
+// The data root as the binary resolves it: AOC_DATA, else data in the project's aoc.toml (or the
+// file named by AOC_CONFIG), relative to that file, else data/
+fn data_root() -> PathBuf {
+    let config = env::var("AOC_CONFIG").map_or(PathBuf::from("aoc.toml"), PathBuf::from);
+    let from_file = fs::read_to_string(&config)
+        .ok()
+        .and_then(|contents| contents.parse::<toml::Table>().ok())
+        .and_then(|table| Some(table.get("data")?.as_str()?.into()));
+    let data = env::var("AOC_DATA").ok().map(PathBuf::from).or(from_file);
+    layout::data_root(&layout::project_root(Some(&config)), data)
+}
+
+// Every year with its own directory under the data root, along with the default year
+fn data_years() -> Vec<u32> {
+    let mut years: Vec<u32> = fs::read_dir(data_root())
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter_map(|entry| entry.file_name().to_str()?.parse().ok())
+        .chain([DEFAULT_YEAR])
+        .collect();
+    years.sort();
+    years.dedup();
+    years
+}
+
+fn data_dir(year: u32, kind: &str) -> PathBuf {
+    layout::data_dir(&data_root(), year, kind)
+}
+
+fn has_expected_answer(year: u32, problem: u32, label: &Option<String>, part: char) -> bool {
+    let answers = fs::read_to_string(data_dir(year, "answers").join(format!("{problem:02}.txt")))
+        .unwrap_or_default();
+    let key = match label {
+        Some(label) => format!("~{part}:{label}"),
+        None => format!("~{part}"),
+    };
+    answers
+        .lines()
+        .any(|line| line.split_once(": ").is_some_and(|(k, _)| k == key))
+}
+
+fn example_tests() -> String {
+    let mut tests = String::new();
+    for year in data_years() {
+        let dir = data_dir(year, "examples");
+        let mut examples: Vec<_> = fs::read_dir(&dir)
+            .into_iter()
+            .flatten()
+            .flatten()
+            .filter_map(|entry| parse_example_name(entry.file_name().to_str()?))
+            .filter(|(problem, _)| Path::new(&source_path(year, *problem)).exists())
+            .collect();
+        examples.sort();
+
+        for (problem, label) in examples {
+            let (file_name, test_name) = match &label {
+                Some(label) => (
+                    format!("{problem:02}-{label}.txt"),
+                    format!("{}_{}", module_name(year, problem), label.to_lowercase()),
+                ),
+                None => (format!("{problem:02}.txt"), module_name(year, problem)),
+            };
+            // A sidecar NN[-label].parts restricts an example to the parts it lists
+            let parts = fs::read_to_string(dir.join(file_name.replace(".txt", ".parts")))
+                .unwrap_or("ab".to_string());
+            let path = dir.join(&file_name).display().to_string();
+            for part in ['a', 'b'].into_iter().filter(|&part| parts.contains(part)) {
+                if !has_expected_answer(year, problem, &label, part) {
+                    tests.push_str("#[ignore = \"no expected answer\"]\n");
+                }
+                tests.push_str(&format!(
+                    "#[test]\nfn {test_name}_{part}() {{\n    check_example(Puzzle::new({year}, {problem}), {label:?}, {path:?}, Part::{});\n}}\n\n",
+                    part.to_ascii_uppercase()
+                ));
+            }
+        }
+    }
+    tests
+}
+
+// Days are named dayNN.rs with exactly two digits, as in Puzzle::source_path
+fn day_number(file_name: &str) -> Option<u32> {
+    let digits = file_name.strip_prefix("day")?.strip_suffix(".rs")?;
+    if digits.len() != 2 || !digits.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    digits.parse().ok().filter(|day| (1..=25).contains(day))
+}
+
+fn days_in(dir: &str) -> Vec<u32> {
+    fs::read_dir(dir)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter_map(|entry| {
+            let file_name = entry.file_name().to_str()?.to_string();
+            let day = day_number(&file_name);
+            if day.is_none() && file_name.starts_with("day") && file_name.ends_with(".rs") {
+                println!(
+                    "cargo:warning=Ignoring {dir}/{file_name}, days are named day01.rs to day25.rs"
+                );
+            }
+            day
+        })
+        .collect()
+}
+
+// Every src/days/dayNN.rs is a day of the default year, and every src/days/<year>/dayNN.rs a day
+// of that year, each declared as a module and registered by its year and number
+fn day_registry() -> String {
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let mut puzzles: Vec<(u32, u32)> = days_in("src/days")
+        .into_iter()
+        .map(|day| (DEFAULT_YEAR, day))
+        .collect();
+    let year_dirs = fs::read_dir("src/days").into_iter().flatten().flatten();
+    for year in year_dirs.filter_map(|entry| entry.file_name().to_str()?.parse::<u32>().ok()) {
+        let days = days_in(&format!("src/days/{year}"));
+        puzzles.extend(days.into_iter().map(|day| (year, day)));
+    }
+    puzzles.sort();
+    puzzles.dedup();
+
+    let mut registry = String::new();
+    for &(year, day) in &puzzles {
+        registry.push_str(&format!(
+            "#[path = {:?}]\nmod {};\n",
+            format!("{manifest_dir}/{}", source_path(year, day)),
+            module_name(year, day)
+        ));
+    }
+    registry.push_str("\nconst DAYS: &[Puzzle] = &[\n");
+    for (year, day) in &puzzles {
+        registry.push_str(&format!("    Puzzle {{ year: {year}, day: {day} }},\n"));
+    }
+    registry.push_str("];\n\n");
+    registry.push_str(
+        "fn build_problem(puzzle: Puzzle) -> Option<Box<dyn DynProblem>> {\n    match (puzzle.year, puzzle.day) {\n",
+    );
+    for &(year, day) in &puzzles {
+        registry.push_str(&format!(
+            "        ({year}, {day}) => Some(Box::new({}::Solution {{}})),\n",
+            module_name(year, day)
+        ));
+    }
+    registry.push_str("        _ => None,\n    }\n}\n");
+    registry
+}
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-changed=src/common/layout.rs");
+    // A new year's data is picked up once its first solution is added under src/days
+    println!("cargo:rerun-if-changed=src/days");
+    println!("cargo:rerun-if-changed=aoc.toml");
+    println!("cargo:rerun-if-env-changed=AOC_CONFIG");
+    println!("cargo:rerun-if-env-changed=AOC_DATA");
+    for year in data_years() {
+        for dir in [data_dir(year, "examples"), data_dir(year, "answers")] {
+            if dir.exists() {
+                println!("cargo:rerun-if-changed={}", dir.display());
+            }
+        }
+    }
+    let out_dir = env::var("OUT_DIR").unwrap();
+    fs::write(Path::new(&out_dir).join("days.rs"), day_registry()).unwrap();
+    fs::write(
+        Path::new(&out_dir).join("example_tests.rs"),
+        example_tests(),
+    )
+    .unwrap();
+}
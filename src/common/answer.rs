@@ -0,0 +1,124 @@
+use num::BigInt;
+use std::cmp::Ordering;
+use std::convert::Infallible;
+use std::fmt::Display;
+use std::str::FromStr;
+
+// The answer to a part, kept numeric where possible so that answers can be summed and compared.
+// Integers compare by value regardless of which variant holds them, so Signed(5) == Unsigned(5).
+#[derive(Debug, Clone)]
+pub enum Answer {
+    Signed(i128),
+    Unsigned(u128),
+    Big(BigInt),
+    Text(String),
+}
+
+impl Answer {
+    pub fn is_number(&self) -> bool {
+        !matches!(self, Answer::Text(_))
+    }
+
+    pub fn to_bigint(&self) -> Option<BigInt> {
+        match self {
+            Answer::Signed(n) => Some(BigInt::from(*n)),
+            Answer::Unsigned(n) => Some(BigInt::from(*n)),
+            Answer::Big(n) => Some(n.clone()),
+            Answer::Text(_) => None,
+        }
+    }
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Answer::Signed(n) => n.fmt(f),
+            Answer::Unsigned(n) => n.fmt(f),
+            Answer::Big(n) => n.fmt(f),
+            Answer::Text(s) => s.fmt(f),
+        }
+    }
+}
+
+// Anything that looks like an integer becomes the smallest variant that holds it, everything else is text
+impl FromStr for Answer {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if let Ok(n) = s.parse() {
+            Ok(Answer::Signed(n))
+        } else if let Ok(n) = s.parse() {
+            Ok(Answer::Unsigned(n))
+        } else if let Ok(n) = s.parse() {
+            Ok(Answer::Big(n))
+        } else {
+            Ok(Answer::Text(s.to_string()))
+        }
+    }
+}
+
+impl PartialEq for Answer {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Answer::Text(a), Answer::Text(b)) => a == b,
+            (a, b) => a.is_number() && b.is_number() && a.to_bigint() == b.to_bigint(),
+        }
+    }
+}
+
+impl Eq for Answer {}
+
+// Numbers are ordered by value; text is only comparable with other text
+impl PartialOrd for Answer {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        match (self, other) {
+            (Answer::Text(a), Answer::Text(b)) => Some(a.cmp(b)),
+            (Answer::Text(_), _) | (_, Answer::Text(_)) => None,
+            (Answer::Signed(a), Answer::Signed(b)) => Some(a.cmp(b)),
+            (Answer::Unsigned(a), Answer::Unsigned(b)) => Some(a.cmp(b)),
+            (a, b) => a.to_bigint().partial_cmp(&b.to_bigint()),
+        }
+    }
+}
+
+macro_rules! from_signed {
+    ($($t:ty),*) => {
+        $(impl From<$t> for Answer {
+            fn from(n: $t) -> Self {
+                Answer::Signed(n as i128)
+            }
+        })*
+    };
+}
+
+macro_rules! from_unsigned {
+    ($($t:ty),*) => {
+        $(impl From<$t> for Answer {
+            fn from(n: $t) -> Self {
+                Answer::Unsigned(n as u128)
+            }
+        })*
+    };
+}
+
+from_signed!(i8, i16, i32, i64, i128, isize);
+from_unsigned!(u8, u16, u32, u64, u128, usize);
+
+impl From<BigInt> for Answer {
+    fn from(n: BigInt) -> Self {
+        Answer::Big(n)
+    }
+}
+
+impl From<String> for Answer {
+    fn from(s: String) -> Self {
+        Answer::Text(s)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(s: &str) -> Self {
+        Answer::Text(s.to_string())
+    }
+}
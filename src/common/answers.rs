@@ -0,0 +1,73 @@
+use aoc_parse::{parser, prelude::*};
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::str::FromStr;
+
+use crate::common::answer::Answer;
+use crate::common::io::{InputKind, Part};
+use crate::common::puzzle::Puzzle;
+
+// Known-good answers for a problem, stored in ./data/<year>/answers/NN.txt as lines like
+//   a: 1234
+//   ~b: 56
+//   ~b:large: 789
+// where a leading ~ marks the answer for an example input, optionally followed by its label
+#[derive(Debug)]
+pub struct AnswerStore {
+    problem: Puzzle,
+    answers: BTreeMap<(InputKind, Part), Answer>,
+}
+
+impl AnswerStore {
+    fn path(problem: Puzzle) -> String {
+        problem.data_path("answers", ".txt")
+    }
+
+    pub fn load(problem: Puzzle) -> Result<Self, aoc_parse::ParseError> {
+        let contents = fs::read_to_string(Self::path(problem)).unwrap_or_default();
+        let part_p = parser!({"a" => Part::A, "b" => Part::B});
+        let key_p = parser!({
+            "~" part:part_p label:(":" string(alnum+))? => (InputKind::Example(label), part),
+            part:part_p => (InputKind::Real, part),
+        });
+        let answers = parser!(lines(key_p ": " string(any_char+)))
+            .parse(&contents)?
+            .into_iter()
+            .map(|(key, answer)| {
+                let Ok(answer) = Answer::from_str(&answer);
+                (key, answer)
+            })
+            .collect();
+        Ok(Self { problem, answers })
+    }
+
+    pub fn problem(&self) -> Puzzle {
+        self.problem
+    }
+
+    pub fn get(&self, input: &InputKind, part: Part) -> Option<&Answer> {
+        self.answers.get(&(input.clone(), part))
+    }
+
+    pub fn set(&mut self, input: &InputKind, part: Part, answer: Answer) {
+        self.answers.insert((input.clone(), part), answer);
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        let contents: String = self
+            .answers
+            .iter()
+            .map(|((input, part), answer)| {
+                let part = part.to_string().to_lowercase();
+                match input {
+                    InputKind::Real => format!("{part}: {answer}\n"),
+                    InputKind::Example(None) => format!("~{part}: {answer}\n"),
+                    InputKind::Example(Some(label)) => format!("~{part}:{label}: {answer}\n"),
+                }
+            })
+            .collect();
+        fs::create_dir_all(self.problem.data_dir("answers"))?;
+        fs::write(Self::path(self.problem), contents)
+    }
+}
@@ -0,0 +1,92 @@
+use colored::Colorize;
+use std::fmt::Display;
+use std::time::{Duration, Instant};
+
+pub const DEFAULT_RUNS: usize = 10;
+pub const BUDGET: Duration = Duration::from_secs(1);
+
+pub fn time<T>(f: impl FnOnce() -> T) -> (T, Duration) {
+    let start = Instant::now();
+    let output = f();
+    (output, start.elapsed())
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub stddev: Duration,
+}
+
+impl Stats {
+    pub fn from_samples(samples: &[Duration]) -> Self {
+        assert!(!samples.is_empty(), "Need at least one sample");
+        let mut sorted = samples.to_vec();
+        sorted.sort();
+        let n = sorted.len();
+        let median = if n % 2 == 1 {
+            sorted[n / 2]
+        } else {
+            (sorted[n / 2 - 1] + sorted[n / 2]) / 2
+        };
+        let mean_secs = sorted.iter().map(Duration::as_secs_f64).sum::<f64>() / n as f64;
+        let variance = sorted
+            .iter()
+            .map(|d| (d.as_secs_f64() - mean_secs).powi(2))
+            .sum::<f64>()
+            / n as f64;
+        Stats {
+            min: sorted[0],
+            median,
+            mean: Duration::from_secs_f64(mean_secs),
+            stddev: Duration::from_secs_f64(variance.sqrt()),
+        }
+    }
+}
+
+impl Display for Stats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "min {:>10.2?} | median {:>10.2?} | mean {:>10.2?} | stddev {:>10.2?}",
+            self.min, self.median, self.mean, self.stddev
+        )
+    }
+}
+
+pub struct BenchReport {
+    pub load: Stats,
+    pub parse: Stats,
+    pub part_a: Option<Stats>,
+    pub part_b: Option<Stats>,
+}
+
+impl BenchReport {
+    // Expected time for a single run of everything that was benchmarked
+    pub fn total_mean(&self) -> Duration {
+        let parts = [Some(self.load), Some(self.parse), self.part_a, self.part_b];
+        parts.iter().flatten().map(|s| s.mean).sum()
+    }
+
+    pub fn print(&self) {
+        println!("Load  : {}", self.load);
+        println!("Parse : {}", self.parse);
+        if let Some(stats) = &self.part_a {
+            println!("A     : {}", stats);
+        }
+        if let Some(stats) = &self.part_b {
+            println!("B     : {}", stats);
+        }
+    }
+}
+
+pub fn print_total(total: Duration) {
+    let total_str = format!("{:.2?}", total);
+    let total_str = if total <= BUDGET {
+        total_str.bold().green()
+    } else {
+        total_str.bold().red()
+    };
+    println!("Total : {total_str} (budget {:?})", BUDGET)
+}
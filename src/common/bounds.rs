@@ -0,0 +1,78 @@
+use std::fmt::Display;
+
+use crate::common::answer::Answer;
+use crate::common::io::Part;
+use crate::common::puzzle::Puzzle;
+use crate::common::submit::{self, Hint, Verdict};
+
+// Why an answer cannot be right, given what the site has already told us
+#[derive(Debug, Clone)]
+pub enum Contradiction {
+    KnownWrong,
+    TooHigh(Answer),
+    TooLow(Answer),
+}
+
+impl Display for Contradiction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Contradiction::KnownWrong => write!(f, "was already submitted and rejected"),
+            Contradiction::TooHigh(bound) => {
+                write!(f, "is not below {bound}, which was rejected as too high")
+            }
+            Contradiction::TooLow(bound) => {
+                write!(f, "is not above {bound}, which was rejected as too low")
+            }
+        }
+    }
+}
+
+// What previous wrong submissions for one part say about its answer
+#[derive(Debug, Default)]
+pub struct Bounds {
+    rejected: Vec<Answer>,
+    // The smallest answer that was too high and the largest that was too low
+    upper: Option<Answer>,
+    lower: Option<Answer>,
+}
+
+impl Bounds {
+    pub fn load(problem: Puzzle, part: Part) -> Self {
+        let submissions = submit::load_log(problem).unwrap_or_else(|e| {
+            eprintln!("Ignoring unreadable submission log for problem {problem}: {e}");
+            vec![]
+        });
+        let mut bounds = Bounds::default();
+        for submission in submissions.into_iter().filter(|s| s.part == part) {
+            let Verdict::Wrong(hint) = submission.verdict else {
+                continue;
+            };
+            let answer = submission.answer;
+            match hint {
+                Some(Hint::TooHigh) if bounds.upper.as_ref().is_none_or(|u| answer < *u) => {
+                    bounds.upper = Some(answer.clone())
+                }
+                Some(Hint::TooLow) if bounds.lower.as_ref().is_none_or(|l| answer > *l) => {
+                    bounds.lower = Some(answer.clone())
+                }
+                _ => {}
+            }
+            bounds.rejected.push(answer);
+        }
+        bounds
+    }
+
+    pub fn contradiction(&self, answer: &Answer) -> Option<Contradiction> {
+        if self.rejected.contains(answer) {
+            return Some(Contradiction::KnownWrong);
+        }
+        // Text answers are incomparable with numbers, so never fall outside numeric bounds
+        if let Some(upper) = self.upper.as_ref().filter(|u| answer >= *u) {
+            return Some(Contradiction::TooHigh(upper.clone()));
+        }
+        if let Some(lower) = self.lower.as_ref().filter(|l| answer <= *l) {
+            return Some(Contradiction::TooLow(lower.clone()));
+        }
+        None
+    }
+}
@@ -0,0 +1,91 @@
+use colored::Colorize;
+
+use crate::common::answer::Answer;
+use crate::common::answers::AnswerStore;
+use crate::common::io::{AocRunError, InputKind, Part, RunCode};
+
+pub enum Verdict {
+    Pass,
+    Fail { expected: Answer },
+    Error(AocRunError),
+    Unknown,
+    Recorded,
+}
+
+fn judge(solution: Option<&Answer>, expected: Option<&Answer>) -> Verdict {
+    match (solution, expected) {
+        (Some(s), Some(e)) if s == e => Verdict::Pass,
+        (_, Some(e)) => Verdict::Fail {
+            expected: e.clone(),
+        },
+        (_, None) => Verdict::Unknown,
+    }
+}
+
+pub fn verdict_line(solution: Option<&Answer>, verdict: &Verdict) -> String {
+    let solution = solution.map_or("TODO".to_string(), Answer::to_string);
+    match verdict {
+        Verdict::Pass => format!("{} {solution}", "PASS".bold().green()),
+        Verdict::Fail { expected } => {
+            format!("{} {solution} (expected {expected})", "FAIL".bold().red())
+        }
+        Verdict::Error(e) => format!("{} {e}", "FAIL".bold().red()),
+        Verdict::Unknown => format!("{} {solution}", "UNKNOWN".bold().yellow()),
+        Verdict::Recorded => format!("{} {solution}", "RECORDED".bold().cyan()),
+    }
+}
+
+fn print_verdict(prefix: &str, solution: Option<&Answer>, verdict: &Verdict) {
+    println!("{prefix} : {}", verdict_line(solution, verdict))
+}
+
+// How a part that has been run compares with the store, including earlier rejected submissions
+pub fn verdict(rc: &RunCode, part: Part, store: &AnswerStore) -> Verdict {
+    if let Some(e) = rc.error(part) {
+        return Verdict::Error(e.clone());
+    }
+    // An answer the site has already rejected cannot pass, nor be recorded as known-good
+    if let Some(e) = rc.bounds_error(part) {
+        return Verdict::Error(e);
+    }
+    judge(rc.solution(part), store.get(rc.input(), part))
+}
+
+// Runs the parts of a run code and compares them against the store.
+// Returns whether every part passed; missing inputs are reported but do not count as failures.
+pub fn check(mut rc: RunCode, store: &mut AnswerStore, record: bool) -> bool {
+    let problem = rc.problem();
+    let input = rc.input().clone();
+    // Names parts like run codes do, e.g. Day 8 ~B:2 for part B of example 2
+    let name = |part: &str| match &input {
+        InputKind::Real => format!("Day {problem} {part}"),
+        InputKind::Example(None) => format!("Day {problem} ~{part}"),
+        InputKind::Example(Some(label)) => format!("Day {problem} ~{part}:{label}"),
+    };
+    match rc.run() {
+        Ok(()) => {}
+        Err(AocRunError::NoFile(path)) => {
+            println!("{} : {}", name(" "), format!("no input at {path}").dimmed());
+            return true;
+        }
+        Err(e) => {
+            println!("{}", e);
+            return false;
+        }
+    };
+    let mut all_pass = true;
+    for part in [Part::A, Part::B].into_iter().filter(|&part| rc.runs(part)) {
+        let solution = rc.solution(part);
+        let verdict = match (verdict(&rc, part, store), solution) {
+            (Verdict::Error(e), _) => Verdict::Error(e),
+            (_, Some(s)) if record => {
+                store.set(&input, part, s.clone());
+                Verdict::Recorded
+            }
+            (verdict, _) => verdict,
+        };
+        all_pass &= !matches!(verdict, Verdict::Fail { .. } | Verdict::Error(_));
+        print_verdict(&name(&part.to_string()), solution, &verdict);
+    }
+    all_pass
+}
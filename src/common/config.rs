@@ -0,0 +1,204 @@
+use serde::Deserialize;
+use std::env;
+use std::fmt::Display;
+use std::fs;
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::sync::OnceLock;
+use std::time::Duration;
+
+use crate::common::layout::{self, DEFAULT_YEAR};
+use crate::common::output::Format;
+
+pub const FILE_NAME: &str = "aoc.toml";
+
+// Settings as written in aoc.toml, every one of them optional
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct Settings {
+    data: Option<PathBuf>,
+    year: Option<u32>,
+    format: Option<String>,
+    timeout: Option<f64>,
+    color: Option<bool>,
+    threads: Option<usize>,
+}
+
+// Project settings, read from aoc.toml in the current directory or the nearest one above it,
+// or from the file named by AOC_CONFIG. Each setting can be overridden by an environment variable,
+// e.g. AOC_YEAR, and command line flags override both.
+#[derive(Debug)]
+pub struct Config {
+    // The file the settings came from, if any
+    pub file: Option<PathBuf>,
+    // The project root, the directory holding the config file or else the current directory
+    pub root: PathBuf,
+    // Where inputs, examples, answers and submissions are kept, relative paths being from the root
+    pub data: PathBuf,
+    // The year a bare day number refers to
+    pub year: u32,
+    pub format: Format,
+    pub timeout: Option<Duration>,
+    // Forces coloured output on or off, rather than detecting whether it is supported
+    pub color: Option<bool>,
+    // The number of threads for --parallel, rather than one per core
+    pub threads: Option<usize>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ConfigError {
+    pub source: String,
+    pub message: String,
+}
+
+impl Display for ConfigError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Invalid configuration in {}: {}",
+            self.source, self.message
+        )
+    }
+}
+
+fn find_file() -> Result<Option<PathBuf>, ConfigError> {
+    if let Ok(path) = env::var("AOC_CONFIG") {
+        let path = PathBuf::from(path);
+        return match path.is_file() {
+            true => Ok(Some(path)),
+            false => Err(ConfigError {
+                source: "AOC_CONFIG".to_string(),
+                message: format!("{} does not exist", path.display()),
+            }),
+        };
+    }
+    let cwd = env::current_dir().unwrap_or_default();
+    Ok(cwd
+        .ancestors()
+        .map(|dir| dir.join(FILE_NAME))
+        .find(|path| path.is_file()))
+}
+
+// The environment variable if it is set, parsed as a T, or else the setting from the file
+fn from_env<T: FromStr>(var: &str, setting: Option<T>) -> Result<Option<T>, ConfigError> {
+    match env::var(var) {
+        Err(_) => Ok(setting),
+        Ok(value) => match value.parse() {
+            Ok(value) => Ok(Some(value)),
+            Err(_) => Err(ConfigError {
+                source: var.to_string(),
+                message: format!("{value:?} is not a valid value"),
+            }),
+        },
+    }
+}
+
+impl Config {
+    pub fn load() -> Result<Self, ConfigError> {
+        let file = find_file()?;
+        let settings = match &file {
+            None => Settings::default(),
+            Some(path) => {
+                let error = |message: String| ConfigError {
+                    source: path.display().to_string(),
+                    message,
+                };
+                let contents = fs::read_to_string(path).map_err(|e| error(e.to_string()))?;
+                toml::from_str(&contents).map_err(|e| error(e.message().to_string()))?
+            }
+        };
+        let source = file
+            .as_ref()
+            .map_or(FILE_NAME.to_string(), |path| path.display().to_string());
+        // Blames the environment variable if it is set, rather than the file
+        let invalid = |var: &str, message: &str| ConfigError {
+            source: match env::var(var) {
+                Ok(_) => var.to_string(),
+                Err(_) => source.clone(),
+            },
+            message: message.to_string(),
+        };
+
+        let root = layout::project_root(file.as_deref());
+        let data = layout::data_root(&root, from_env("AOC_DATA", settings.data)?);
+        let year = from_env("AOC_YEAR", settings.year)?.unwrap_or(DEFAULT_YEAR);
+        let format = match from_env("AOC_FORMAT", settings.format)? {
+            None => Format::Text,
+            Some(format) => format
+                .parse()
+                .map_err(|e: String| invalid("AOC_FORMAT", &e))?,
+        };
+        let timeout = match from_env("AOC_TIMEOUT", settings.timeout)? {
+            None => None,
+            Some(secs) => Some(
+                Duration::try_from_secs_f64(secs)
+                    .ok()
+                    .filter(|limit| !limit.is_zero())
+                    .ok_or_else(|| {
+                        invalid(
+                            "AOC_TIMEOUT",
+                            "timeout must be a positive number of seconds",
+                        )
+                    })?,
+            ),
+        };
+        let color = from_env("AOC_COLOR", settings.color)?;
+        let threads = from_env("AOC_THREADS", settings.threads)?;
+        if threads == Some(0) {
+            return Err(invalid("AOC_THREADS", "threads must be a positive integer"));
+        }
+        Ok(Config {
+            file,
+            root,
+            data,
+            year,
+            format,
+            timeout,
+            color,
+            threads,
+        })
+    }
+
+    // A path within the project, e.g. src/days
+    pub fn project_path(&self, path: &str) -> PathBuf {
+        self.root.join(path)
+    }
+
+    // Which file and data directory were resolved, for the runner to report
+    pub fn summary(&self) -> String {
+        let file = self
+            .file
+            .as_ref()
+            .map_or(format!("no {FILE_NAME}"), |f| f.display().to_string());
+        format!("Config {file}, data in {}", self.data.display())
+    }
+
+    pub fn print(&self) {
+        let setting = |value: Option<String>| value.unwrap_or("default".to_string());
+        println!("{}", self.summary());
+        println!("Year    : {}", self.year);
+        println!("Format  : {}", self.format);
+        println!(
+            "Timeout : {}",
+            setting(self.timeout.map(|t| format!("{t:?}")))
+        );
+        println!("Color   : {}", setting(self.color.map(|c| c.to_string())));
+        println!("Threads : {}", setting(self.threads.map(|t| t.to_string())));
+    }
+}
+
+static CONFIG: OnceLock<Config> = OnceLock::new();
+
+// Loads the configuration for the rest of the run; only the first call has any effect
+pub fn init() -> Result<&'static Config, ConfigError> {
+    if let Some(config) = CONFIG.get() {
+        return Ok(config);
+    }
+    let config = Config::load()?;
+    Ok(CONFIG.get_or_init(|| config))
+}
+
+// The configuration, loaded on first use if init has not been called, as in tests
+pub fn get() -> &'static Config {
+    init().unwrap_or_else(|e| panic!("{e}"))
+}
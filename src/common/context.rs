@@ -0,0 +1,67 @@
+use aoc_parse::{parser, prelude::*};
+use std::any::type_name;
+use std::collections::BTreeMap;
+use std::fs;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use crate::common::problem::SolverError;
+
+// Puzzle parameters for one input, read from a sidecar next to it with the same name ending
+// .params instead of .txt, e.g. data/examples/02.params, holding lines like
+//   red: 12
+// so that examples and real inputs can use different constants without editing source
+#[derive(Debug, Default, Clone)]
+pub struct Context {
+    params: BTreeMap<String, String>,
+    // Raised by the runner when the part runs out of time
+    cancelled: Arc<AtomicBool>,
+}
+
+impl Context {
+    // A missing sidecar just means every parameter takes its default
+    pub fn load(path: &str) -> Result<Self, aoc_parse::ParseError> {
+        let contents = fs::read_to_string(path).unwrap_or_default();
+        let key_p = parser!(string({_c:alnum => (), "_" => ()}+));
+        let params = parser!(lines(key_p ": " string(any_char+)))
+            .parse(&contents)?
+            .into_iter()
+            .collect();
+        Ok(Self {
+            params,
+            cancelled: Arc::default(),
+        })
+    }
+
+    // The same parameters, for a part that is cancelled by raising the flag
+    pub fn with_cancel_flag(&self, cancelled: Arc<AtomicBool>) -> Self {
+        Context {
+            params: self.params.clone(),
+            cancelled,
+        }
+    }
+
+    // Parts that could run for a long time call this every so often, so that once they have timed
+    // out they stop rather than carrying on in the background after the runner has moved on
+    pub fn check_cancelled(&self) -> Result<(), SolverError> {
+        if self.cancelled.load(Ordering::Relaxed) {
+            Err(SolverError::Cancelled)
+        } else {
+            Ok(())
+        }
+    }
+
+    // The parameter parsed as a T, or the default if the input does not set it
+    pub fn get_or<T: FromStr>(&self, key: &str, default: T) -> Result<T, SolverError> {
+        match self.params.get(key) {
+            None => Ok(default),
+            Some(value) => value.trim().parse().map_err(|_| {
+                SolverError::InvalidInput(format!(
+                    "parameter {key} = {value:?} is not a valid {}",
+                    type_name::<T>()
+                ))
+            }),
+        }
+    }
+}
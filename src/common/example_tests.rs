@@ -0,0 +1,34 @@
+use std::fs;
+
+use crate::common::answers::AnswerStore;
+use crate::common::context::Context;
+use crate::common::io::{InputKind, Part};
+use crate::common::problem::SolverError;
+use crate::common::puzzle::Puzzle;
+use crate::days;
+
+// Test cases are generated by build.rs, one per example input and part
+fn check_example(problem: Puzzle, label: Option<&str>, path: &str, part: Part) {
+    let input = fs::read_to_string(path).expect("Example input should exist");
+    let solver = days::get_problem(problem).unwrap_or_else(|e| panic!("{e}"));
+    let store = AnswerStore::load(problem).expect("Valid answers file");
+    let input_kind = InputKind::Example(label.map(str::to_string));
+    let expected = store
+        .get(&input_kind, part)
+        .unwrap_or_else(|| panic!("No expected answer for day {problem} part {part} on {path}"));
+    let params = format!("{}.params", path.strip_suffix(".txt").unwrap_or(path));
+    let ctx = Context::load(&params).unwrap_or_else(|e| panic!("Invalid {params}: {e}"));
+    let solution = solver.parse(&input).and_then(|parsed| match part {
+        Part::A => solver.solve_a(&*parsed, &ctx),
+        Part::B => solver.solve_b(&*parsed, &ctx),
+    });
+    let found = match &solution {
+        Ok(answer) if answer == expected => return,
+        Ok(answer) => answer.to_string(),
+        Err(SolverError::NotImplemented) => "TODO".to_string(),
+        Err(e) => format!("error: {e}"),
+    };
+    panic!("\nDay {problem} part {part} on {path}\n  expected: {expected}\n     found: {found}\n");
+}
+
+include!(concat!(env!("OUT_DIR"), "/example_tests.rs"));
@@ -0,0 +1,68 @@
+use std::fs;
+
+use crate::common::puzzle::Puzzle;
+
+// An example input, data/<year>/examples/NN.txt or data/<year>/examples/NN-<label>.txt.
+// By default an example applies to both parts; a sidecar file with the same name ending .parts
+// instead of .txt, containing a, b or ab, restricts it to those parts.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Example {
+    pub label: Option<String>,
+    pub part_a: bool,
+    pub part_b: bool,
+}
+
+fn stem(problem: Puzzle, label: &Option<String>) -> String {
+    match label {
+        Some(label) => problem.data_path("examples", &format!("-{label}")),
+        None => problem.data_path("examples", ""),
+    }
+}
+
+pub fn path(problem: Puzzle, label: &Option<String>) -> String {
+    format!("{}.txt", stem(problem, label))
+}
+
+impl Example {
+    pub fn load(problem: Puzzle, label: Option<String>) -> Self {
+        let declared = fs::read_to_string(format!("{}.parts", stem(problem, &label)));
+        let (part_a, part_b) = match declared {
+            Ok(parts) => (parts.contains('a'), parts.contains('b')),
+            Err(_) => (true, true),
+        };
+        Example {
+            label,
+            part_a,
+            part_b,
+        }
+    }
+}
+
+// Every example for a problem, the unlabelled one first and then by label
+pub fn list(problem: Puzzle) -> Vec<Example> {
+    let prefix = format!("{:02}", problem.day);
+    let mut labels: Vec<Option<String>> = fs::read_dir(problem.data_dir("examples"))
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter_map(|entry| {
+            let file_name = entry.file_name();
+            let stem = file_name.to_str()?.strip_suffix(".txt")?;
+            match stem.strip_prefix(&prefix)? {
+                "" => Some(None),
+                rest => {
+                    let label = rest.strip_prefix('-')?;
+                    label
+                        .chars()
+                        .all(char::is_alphanumeric)
+                        .then(|| Some(label.to_string()))
+                }
+            }
+        })
+        .collect();
+    labels.sort();
+    labels
+        .into_iter()
+        .map(|label| Example::load(problem, label))
+        .collect()
+}
@@ -0,0 +1,210 @@
+use std::env;
+use std::fmt::Display;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::Path;
+
+use crate::common::config;
+use crate::common::http::{self, Response};
+use crate::common::puzzle::Puzzle;
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+// The site asks automated tools to identify themselves
+pub const USER_AGENT: &str = "github.com/tomchaplin/aoc_2023 (aoc_2023 input fetcher)";
+
+// AOC_BASE_URL points requests somewhere other than adventofcode.com, e.g. a local mock server
+pub fn base_url() -> String {
+    env::var("AOC_BASE_URL")
+        .unwrap_or(DEFAULT_BASE_URL.to_string())
+        .trim_end_matches('/')
+        .to_string()
+}
+
+// The session cookie, from the AOC_SESSION environment variable or else from a line in the
+// project's .env like
+//   AOC_SESSION="<your_session_cookie>"
+pub fn session() -> Option<String> {
+    if let Ok(session) = env::var("AOC_SESSION") {
+        return Some(session);
+    }
+    fs::read_to_string(config::get().project_path(".env"))
+        .ok()?
+        .lines()
+        .find_map(|line| {
+            let line = line.trim().trim_start_matches("export ");
+            let value = line.strip_prefix("AOC_SESSION=")?;
+            let value = value.trim().trim_matches(|c| c == '"' || c == '\'');
+            (!value.is_empty()).then(|| value.to_string())
+        })
+}
+
+pub fn input_path(problem: Puzzle) -> String {
+    problem.data_path("inputs", ".txt")
+}
+
+#[derive(Debug, Clone)]
+pub enum FetchError {
+    NoSession,
+    SessionExpired,
+    NotUnlocked(Puzzle),
+    Status(u16),
+    Io(String),
+}
+
+impl FetchError {
+    pub fn kind(&self) -> &'static str {
+        match self {
+            FetchError::NoSession => "NoSession",
+            FetchError::SessionExpired => "SessionExpired",
+            FetchError::NotUnlocked(_) => "NotUnlocked",
+            FetchError::Status(_) => "HttpStatus",
+            FetchError::Io(_) => "Io",
+        }
+    }
+}
+
+impl From<io::Error> for FetchError {
+    fn from(error: io::Error) -> Self {
+        FetchError::Io(error.to_string())
+    }
+}
+
+impl Display for FetchError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FetchError::NoSession => write!(f, "no AOC_SESSION set in the environment or .env"),
+            FetchError::SessionExpired => {
+                write!(
+                    f,
+                    "the session was rejected, log in again and update AOC_SESSION"
+                )
+            }
+            FetchError::NotUnlocked(problem) => write!(f, "day {problem} has not unlocked yet"),
+            FetchError::Status(status) => write!(f, "unexpected HTTP status {status}"),
+            FetchError::Io(message) => write!(f, "{message}"),
+        }
+    }
+}
+
+// Turns error pages into errors, so they are never saved as an input or read as a verdict
+pub fn check_response(problem: Puzzle, response: &Response) -> Result<(), FetchError> {
+    match response.status {
+        _ if response.is_success() => Ok(()),
+        400 | 401 | 403 => Err(FetchError::SessionExpired),
+        _ if response.body.contains("log in") => Err(FetchError::SessionExpired),
+        404 if response.body.contains("before it unlocks") => Err(FetchError::NotUnlocked(problem)),
+        status => Err(FetchError::Status(status)),
+    }
+}
+
+#[derive(Debug)]
+pub enum Fetched {
+    Cached(String),
+    Downloaded(String),
+}
+
+// Downloads the real input for a problem, unless it is already cached in ./data/<year>/inputs
+pub fn fetch_input(problem: Puzzle) -> Result<Fetched, FetchError> {
+    download_input(problem, &base_url(), session(), &input_path(problem))
+}
+
+// As fetch_input, with the server, session and cached input's path given explicitly
+fn download_input(
+    problem: Puzzle,
+    base_url: &str,
+    session: Option<String>,
+    path: &str,
+) -> Result<Fetched, FetchError> {
+    let path = Path::new(path);
+    if path.exists() {
+        return Ok(Fetched::Cached(path.display().to_string()));
+    }
+    let session = session.ok_or(FetchError::NoSession)?;
+    let url = format!("{base_url}/{}/day/{}/input", problem.year, problem.day);
+    let cookie = format!("session={session}");
+    let response = http::get(&url, &[("Cookie", &cookie), ("User-Agent", USER_AGENT)])?;
+    check_response(problem, &response)?;
+
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let written = path.display().to_string();
+    match OpenOptions::new().write(true).create_new(true).open(path) {
+        Ok(mut file) => file.write_all(response.body.as_bytes())?,
+        // Someone else fetched it first, keep theirs
+        Err(e) if e.kind() == io::ErrorKind::AlreadyExists => return Ok(Fetched::Cached(written)),
+        Err(e) => return Err(e.into()),
+    }
+    Ok(Fetched::Downloaded(written))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+    use std::process;
+
+    use super::*;
+    use crate::common::http::stub::serve_once;
+
+    fn temp_input(name: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!("aoc-fetch-{}-{name}", process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir.join("inputs").join("05.txt")
+    }
+
+    fn fetch(base_url: &str, path: &Path) -> Result<Fetched, FetchError> {
+        let session = Some("abc123".to_string());
+        download_input(
+            Puzzle::new(2023, 5),
+            base_url,
+            session,
+            path.to_str().unwrap(),
+        )
+    }
+
+    #[test]
+    fn downloads_and_saves_the_input() {
+        let (base_url, server) = serve_once("200 OK", "seeds: 1 2\n");
+        let path = temp_input("ok");
+        let fetched = fetch(&base_url, &path).unwrap();
+        assert!(matches!(fetched, Fetched::Downloaded(_)));
+        assert_eq!(fs::read_to_string(&path).unwrap(), "seeds: 1 2\n");
+        let head = server.join().unwrap();
+        assert!(head.starts_with("GET /2023/day/5/input "), "{head}");
+        assert!(head.contains("session=abc123"), "{head}");
+
+        // A second fetch never reaches the network
+        assert!(matches!(fetch(&base_url, &path), Ok(Fetched::Cached(_))));
+        let _ = fs::remove_dir_all(path.parent().unwrap().parent().unwrap());
+    }
+
+    #[test]
+    fn rejected_session_is_an_error() {
+        let body = "Puzzle inputs differ by user.  Please log in to get your puzzle input.\n";
+        let (base_url, server) = serve_once("400 Bad Request", body);
+        let path = temp_input("expired");
+        let result = fetch(&base_url, &path);
+        assert!(
+            matches!(result, Err(FetchError::SessionExpired)),
+            "{result:?}"
+        );
+        assert!(!path.exists());
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn locked_day_is_an_error() {
+        let body = "Please don't repeatedly request this endpoint before it unlocks! \
+                    The calendar countdown is synchronized with the server time; \
+                    the link will be enabled on the calendar the instant this puzzle becomes available.\n";
+        let (base_url, server) = serve_once("404 Not Found", body);
+        let path = temp_input("locked");
+        let result = fetch(&base_url, &path);
+        assert!(
+            matches!(result, Err(FetchError::NotUnlocked(p)) if p == Puzzle::new(2023, 5)),
+            "{result:?}"
+        );
+        assert!(!path.exists());
+        server.join().unwrap();
+    }
+}
@@ -0,0 +1,286 @@
+use std::ops::{Add, AddAssign, Mul, Neg, Sub};
+
+use crate::common::grid::Pos;
+
+// A point on the plane in screen coordinates, x growing to the right and y growing downwards,
+// so that a grid position (row, column) is the point (column, row)
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Point {
+    pub x: i64,
+    pub y: i64,
+}
+
+impl Point {
+    pub const ORIGIN: Point = Point { x: 0, y: 0 };
+
+    pub fn new(x: i64, y: i64) -> Self {
+        Point { x, y }
+    }
+
+    #[allow(dead_code)]
+    pub fn manhattan(&self, other: Point) -> u64 {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y)
+    }
+
+    pub fn step(&self, direction: Direction) -> Point {
+        *self + direction.delta()
+    }
+
+    // The next point in the direction, if it is still within the rectangle
+    pub fn step_within(&self, direction: Direction, rect: &Rect) -> Option<Point> {
+        Some(self.step(direction)).filter(|next| rect.contains(*next))
+    }
+
+    // The grid position of a point, if it has no negative coordinates
+    pub fn to_pos(self) -> Option<Pos> {
+        Some((self.y.try_into().ok()?, self.x.try_into().ok()?))
+    }
+}
+
+impl From<Pos> for Point {
+    fn from((row, column): Pos) -> Self {
+        Point::new(column as i64, row as i64)
+    }
+}
+
+impl Add for Point {
+    type Output = Point;
+
+    fn add(self, other: Point) -> Point {
+        Point::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+impl AddAssign for Point {
+    fn add_assign(&mut self, other: Point) {
+        *self = *self + other;
+    }
+}
+
+impl Sub for Point {
+    type Output = Point;
+
+    fn sub(self, other: Point) -> Point {
+        Point::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+impl Neg for Point {
+    type Output = Point;
+
+    fn neg(self) -> Point {
+        Point::new(-self.x, -self.y)
+    }
+}
+
+impl Mul<i64> for Point {
+    type Output = Point;
+
+    fn mul(self, scale: i64) -> Point {
+        Point::new(self.x * scale, self.y * scale)
+    }
+}
+
+// The four cardinal directions and the four diagonals between them, with North pointing up
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Direction {
+    North,
+    NorthEast,
+    East,
+    SouthEast,
+    South,
+    SouthWest,
+    West,
+    NorthWest,
+}
+
+impl Direction {
+    pub const CARDINALS: [Direction; 4] = [
+        Direction::North,
+        Direction::East,
+        Direction::South,
+        Direction::West,
+    ];
+
+    // Clockwise from North
+    pub const ALL: [Direction; 8] = [
+        Direction::North,
+        Direction::NorthEast,
+        Direction::East,
+        Direction::SouthEast,
+        Direction::South,
+        Direction::SouthWest,
+        Direction::West,
+        Direction::NorthWest,
+    ];
+
+    // Eighths of a turn clockwise from North
+    fn eighths(&self) -> usize {
+        *self as usize
+    }
+
+    fn from_eighths(eighths: usize) -> Self {
+        Self::ALL[eighths % 8]
+    }
+
+    pub fn delta(&self) -> Point {
+        let (x, y) = match self {
+            Direction::North => (0, -1),
+            Direction::NorthEast => (1, -1),
+            Direction::East => (1, 0),
+            Direction::SouthEast => (1, 1),
+            Direction::South => (0, 1),
+            Direction::SouthWest => (-1, 1),
+            Direction::West => (-1, 0),
+            Direction::NorthWest => (-1, -1),
+        };
+        Point::new(x, y)
+    }
+
+    #[allow(dead_code)]
+    pub fn is_diagonal(&self) -> bool {
+        self.eighths() % 2 == 1
+    }
+
+    // Along a row, East or West
+    pub fn is_horizontal(&self) -> bool {
+        matches!(self, Direction::East | Direction::West)
+    }
+
+    // Along a column, North or South
+    #[allow(dead_code)]
+    pub fn is_vertical(&self) -> bool {
+        matches!(self, Direction::North | Direction::South)
+    }
+
+    // A quarter turn anticlockwise
+    #[allow(dead_code)]
+    pub fn turn_left(&self) -> Self {
+        Self::from_eighths(self.eighths() + 6)
+    }
+
+    // A quarter turn clockwise
+    #[allow(dead_code)]
+    pub fn turn_right(&self) -> Self {
+        Self::from_eighths(self.eighths() + 2)
+    }
+
+    #[allow(dead_code)]
+    pub fn reverse(&self) -> Self {
+        Self::from_eighths(self.eighths() + 4)
+    }
+}
+
+// An axis-aligned rectangle of points, including both corners
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rect {
+    pub min: Point,
+    pub max: Point,
+}
+
+impl Rect {
+    // Width by height points, with the origin in the top left
+    pub fn of_size(width: usize, height: usize) -> Self {
+        Rect {
+            min: Point::ORIGIN,
+            max: Point::new(width as i64 - 1, height as i64 - 1),
+        }
+    }
+
+    // The smallest rectangle containing every point, or None if there are none
+    #[allow(dead_code)]
+    pub fn enclosing(points: impl IntoIterator<Item = Point>) -> Option<Self> {
+        points.into_iter().fold(None, |rect, p| {
+            Some(match rect {
+                None => Rect { min: p, max: p },
+                Some(Rect { min, max }) => Rect {
+                    min: Point::new(min.x.min(p.x), min.y.min(p.y)),
+                    max: Point::new(max.x.max(p.x), max.y.max(p.y)),
+                },
+            })
+        })
+    }
+
+    pub fn contains(&self, p: Point) -> bool {
+        (self.min.x..=self.max.x).contains(&p.x) && (self.min.y..=self.max.y).contains(&p.y)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Direction::*;
+    use super::*;
+
+    #[test]
+    fn turning() {
+        assert_eq!(North.turn_right(), East);
+        assert_eq!(West.turn_right(), North);
+        assert_eq!(North.turn_left(), West);
+        assert_eq!(NorthEast.turn_right(), SouthEast);
+        assert_eq!(NorthEast.turn_left(), NorthWest);
+        assert_eq!(SouthWest.turn_right(), NorthWest);
+        assert_eq!(NorthWest.turn_left(), SouthWest);
+        assert_eq!(NorthEast.reverse(), SouthWest);
+        assert_eq!(South.reverse(), North);
+        for direction in Direction::ALL {
+            assert_eq!(direction.turn_left().turn_right(), direction);
+            assert_eq!(direction.turn_right().turn_right(), direction.reverse());
+            assert_eq!(direction.reverse().reverse(), direction);
+            let turned = (0..4).fold(direction, |d, _| d.turn_left());
+            assert_eq!(turned, direction);
+            assert_eq!(direction.reverse().delta(), -direction.delta());
+            assert_eq!(
+                direction.turn_right().is_diagonal(),
+                direction.is_diagonal()
+            );
+        }
+    }
+
+    #[test]
+    fn classifies_directions() {
+        assert_eq!(Direction::ALL.iter().filter(|d| d.is_diagonal()).count(), 4);
+        assert!(Direction::CARDINALS.iter().all(|d| !d.is_diagonal()));
+        assert!(East.is_horizontal() && !East.is_vertical());
+        assert!(South.is_vertical() && !South.is_horizontal());
+        assert!(!NorthEast.is_horizontal() && !NorthEast.is_vertical());
+        // y grows downwards
+        assert_eq!(North.delta(), Point::new(0, -1));
+        assert_eq!(SouthEast.delta(), Point::new(1, 1));
+    }
+
+    #[test]
+    fn points() {
+        let p = Point::new(3, -4);
+        assert_eq!(p.manhattan(Point::ORIGIN), 7);
+        assert_eq!(p.manhattan(Point::new(-1, 2)), 10);
+        assert_eq!(p.manhattan(p), 0);
+        assert_eq!(p + Point::new(1, 1), Point::new(4, -3));
+        assert_eq!(p - Point::new(1, 1), Point::new(2, -5));
+        assert_eq!(p * 2, Point::new(6, -8));
+        assert_eq!(p.step(NorthWest), Point::new(2, -5));
+        assert_eq!(Point::from((2, 5)), Point::new(5, 2));
+        assert_eq!(Point::new(5, 2).to_pos(), Some((2, 5)));
+        assert_eq!(p.to_pos(), None);
+    }
+
+    #[test]
+    fn rects() {
+        let rect = Rect::of_size(3, 2);
+        assert!(rect.contains(Point::ORIGIN));
+        assert!(rect.contains(Point::new(2, 1)));
+        assert!(!rect.contains(Point::new(3, 1)));
+        assert!(!rect.contains(Point::new(0, -1)));
+
+        let corner = Point::new(2, 1);
+        assert_eq!(corner.step_within(East, &rect), None);
+        assert_eq!(corner.step_within(SouthWest, &rect), None);
+        assert_eq!(corner.step_within(NorthWest, &rect), Some(Point::new(1, 0)));
+
+        assert_eq!(Rect::enclosing([]), None);
+        let points = [Point::new(1, 5), Point::new(-2, 3), Point::new(4, -1)];
+        let enclosing = Rect::enclosing(points).unwrap();
+        assert_eq!(enclosing.min, Point::new(-2, -1));
+        assert_eq!(enclosing.max, Point::new(4, 5));
+        assert!(points.into_iter().all(|p| enclosing.contains(p)));
+    }
+}
@@ -0,0 +1,327 @@
+use std::fmt::Display;
+use std::ops::{Index, IndexMut};
+
+use crate::common::geom::{Direction, Point, Rect};
+use crate::common::problem::SolverError;
+
+// A position in a grid as (row, column), with (0, 0) the top left
+pub type Pos = (usize, usize);
+
+// A rectangular grid, stored row by row in a single Vec
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    // One cell per character of each line, mapped by f. Lines must all be the same length,
+    // though trailing blank lines, as often left when pasting an example, are ignored.
+    pub fn parse(input: &str, mut f: impl FnMut(char) -> T) -> Result<Self, SolverError> {
+        Self::try_parse(input, |c| Some(f(c)))
+    }
+
+    // As parse, but f returns None for characters that are not allowed in the grid
+    pub fn try_parse(
+        input: &str,
+        mut f: impl FnMut(char) -> Option<T>,
+    ) -> Result<Self, SolverError> {
+        let mut cells = vec![];
+        let mut width = None;
+        let mut height = 0;
+        for (row, line) in input.trim_end_matches(['\n', '\r']).lines().enumerate() {
+            let before = cells.len();
+            for (column, c) in line.chars().enumerate() {
+                let cell = f(c).ok_or_else(|| {
+                    SolverError::InvalidInput(format!(
+                        "unexpected {c:?} in grid at line {}, column {}",
+                        row + 1,
+                        column + 1
+                    ))
+                })?;
+                cells.push(cell);
+            }
+            let line_width = cells.len() - before;
+            match width {
+                None => width = Some(line_width),
+                Some(width) if width != line_width => {
+                    return Err(SolverError::InvalidInput(format!(
+                        "grid line {} has {line_width} cells but the first has {width}",
+                        row + 1
+                    )))
+                }
+                Some(_) => {}
+            }
+            height += 1;
+        }
+        Ok(Grid {
+            width: width.unwrap_or(0),
+            height,
+            cells,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, (row, column): Pos) -> bool {
+        row < self.height && column < self.width
+    }
+
+    fn index_of(&self, pos: Pos) -> Option<usize> {
+        self.contains(pos).then(|| pos.0 * self.width + pos.1)
+    }
+
+    pub fn get(&self, pos: Pos) -> Option<&T> {
+        self.index_of(pos).map(|i| &self.cells[i])
+    }
+
+    pub fn get_mut(&mut self, pos: Pos) -> Option<&mut T> {
+        self.index_of(pos).map(|i| &mut self.cells[i])
+    }
+
+    // The grid's positions as points
+    pub fn rect(&self) -> Rect {
+        Rect::of_size(self.width, self.height)
+    }
+
+    // The adjacent position in the direction, if that is still within the grid
+    pub fn step(&self, pos: Pos, direction: Direction) -> Option<Pos> {
+        Point::from(pos)
+            .step_within(direction, &self.rect())?
+            .to_pos()
+    }
+
+    // The orthogonally adjacent positions within the grid
+    #[allow(dead_code)]
+    pub fn neighbours4(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        Direction::CARDINALS
+            .into_iter()
+            .filter_map(move |direction| self.step(pos, direction))
+    }
+
+    // The orthogonally and diagonally adjacent positions within the grid
+    pub fn neighbours8(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        Direction::ALL
+            .into_iter()
+            .filter_map(move |direction| self.step(pos, direction))
+    }
+
+    // Every position, row by row
+    pub fn positions(&self) -> impl Iterator<Item = Pos> {
+        let width = self.width;
+        (0..self.height).flat_map(move |row| (0..width).map(move |column| (row, column)))
+    }
+
+    // Every position along with its cell, row by row
+    pub fn iter(&self) -> impl Iterator<Item = (Pos, &T)> {
+        self.positions().zip(&self.cells)
+    }
+
+    pub fn row(&self, row: usize) -> &[T] {
+        &self.cells[row * self.width..(row + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        (0..self.height).map(|row| self.row(row))
+    }
+
+    #[allow(dead_code)]
+    pub fn column(&self, column: usize) -> impl Iterator<Item = &T> {
+        self.cells.iter().skip(column).step_by(self.width.max(1))
+    }
+
+    #[allow(dead_code)]
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|column| self.column(column))
+    }
+
+    // The first position, row by row, whose cell satisfies the predicate
+    pub fn find(&self, mut pred: impl FnMut(&T) -> bool) -> Option<Pos> {
+        self.iter().find(|(_, cell)| pred(cell)).map(|(pos, _)| pos)
+    }
+
+    pub fn positions_where<'a>(
+        &'a self,
+        mut pred: impl FnMut(&T) -> bool + 'a,
+    ) -> impl Iterator<Item = Pos> + 'a {
+        self.iter()
+            .filter(move |(_, cell)| pred(cell))
+            .map(|(pos, _)| pos)
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn new(width: usize, height: usize, fill: T) -> Self {
+        Grid {
+            width,
+            height,
+            cells: vec![fill; width * height],
+        }
+    }
+
+    // Swaps rows and columns, so that (row, column) moves to (column, row)
+    #[allow(dead_code)]
+    pub fn transpose(&self) -> Self {
+        Grid {
+            width: self.height,
+            height: self.width,
+            cells: self.columns().flatten().cloned().collect(),
+        }
+    }
+
+    // A quarter turn clockwise, so the first column becomes the first row reversed
+    #[allow(dead_code)]
+    pub fn rotate_clockwise(&self) -> Self {
+        Grid {
+            width: self.height,
+            height: self.width,
+            cells: self
+                .columns()
+                .flat_map(|column| column.collect::<Vec<_>>().into_iter().rev())
+                .cloned()
+                .collect(),
+        }
+    }
+
+    // A quarter turn anticlockwise, so the last column becomes the first row
+    #[allow(dead_code)]
+    pub fn rotate_anticlockwise(&self) -> Self {
+        Grid {
+            width: self.height,
+            height: self.width,
+            cells: (0..self.width)
+                .rev()
+                .flat_map(|column| self.column(column))
+                .cloned()
+                .collect(),
+        }
+    }
+}
+
+// Out of bounds positions panic, use get for a checked lookup
+impl<T> Index<Pos> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Pos) -> &T {
+        let (width, height) = (self.width, self.height);
+        self.get(pos)
+            .unwrap_or_else(|| panic!("{pos:?} is outside the {width}x{height} grid"))
+    }
+}
+
+impl<T> IndexMut<Pos> for Grid<T> {
+    fn index_mut(&mut self, pos: Pos) -> &mut T {
+        let (width, height) = (self.width, self.height);
+        self.get_mut(pos)
+            .unwrap_or_else(|| panic!("{pos:?} is outside the {width}x{height} grid"))
+    }
+}
+
+// One line per row, with the cells of each row written next to each other
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                cell.fmt(f)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid(input: &str) -> Grid<char> {
+        Grid::parse(input, |c| c).unwrap()
+    }
+
+    #[test]
+    fn parses_rows_of_cells() {
+        let g = grid("abc\ndef\n");
+        assert_eq!((g.width(), g.height()), (3, 2));
+        assert_eq!(g[(1, 0)], 'd');
+        assert_eq!(g.get((2, 0)), None);
+        assert_eq!(g.get((0, 3)), None);
+        assert_eq!(g.row(1), ['d', 'e', 'f']);
+        assert_eq!(
+            g.columns()
+                .map(|column| column.collect::<String>())
+                .collect::<Vec<_>>(),
+            ["ad", "be", "cf"]
+        );
+        assert_eq!(g.to_string(), "abc\ndef\n");
+    }
+
+    #[test]
+    fn trailing_blank_lines_are_ignored() {
+        assert_eq!(grid("ab\ncd\n\n\n"), grid("ab\ncd"));
+        assert_eq!(grid("ab\r\ncd\r\n\r\n"), grid("ab\ncd"));
+        assert_eq!(grid("").height(), 0);
+    }
+
+    #[test]
+    fn ragged_lines_are_an_error() {
+        let error = Grid::parse("abc\nde\nfgh", |c| c).unwrap_err();
+        assert!(
+            matches!(&error, SolverError::InvalidInput(message) if message.contains("line 2")),
+            "{error:?}"
+        );
+        // A blank line within the grid is a ragged line too
+        assert!(Grid::parse("ab\n\ncd", |c| c).is_err());
+    }
+
+    #[test]
+    fn unexpected_characters_are_an_error() {
+        let error = Grid::try_parse("..\n.x", |c| (c == '.').then_some(())).unwrap_err();
+        assert!(
+            matches!(&error, SolverError::InvalidInput(message) if message.contains("'x'") && message.contains("line 2, column 2")),
+            "{error:?}"
+        );
+    }
+
+    #[test]
+    fn corner_neighbours() {
+        let g = grid("abc\ndef\nghi");
+        let mut corner4: Vec<_> = g.neighbours4((0, 0)).collect();
+        corner4.sort();
+        assert_eq!(corner4, [(0, 1), (1, 0)]);
+        let mut corner8: Vec<_> = g.neighbours8((2, 2)).collect();
+        corner8.sort();
+        assert_eq!(corner8, [(1, 1), (1, 2), (2, 1)]);
+        assert_eq!(g.neighbours4((1, 1)).count(), 4);
+        assert_eq!(g.neighbours8((1, 1)).count(), 8);
+        assert_eq!(g.step((0, 2), Direction::East), None);
+        assert_eq!(g.step((0, 2), Direction::SouthWest), Some((1, 1)));
+    }
+
+    #[test]
+    fn transforms() {
+        let g = grid("abc\ndef");
+        assert_eq!(g.transpose(), grid("ad\nbe\ncf"));
+        assert_eq!(g.transpose().transpose(), g);
+        assert_eq!(g.rotate_clockwise(), grid("da\neb\nfc"));
+        assert_eq!(g.rotate_anticlockwise(), grid("cf\nbe\nad"));
+        assert_eq!(g.rotate_clockwise().rotate_anticlockwise(), g);
+        assert_eq!(g.rotate_anticlockwise().rotate_clockwise(), g);
+        let rotated = (0..4).fold(g.clone(), |g, _| g.rotate_clockwise());
+        assert_eq!(rotated, g);
+    }
+}
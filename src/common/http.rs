@@ -0,0 +1,90 @@
+use std::io;
+
+// A response from adventofcode.com, or from a local mock server in its place
+#[derive(Debug)]
+pub struct Response {
+    pub status: u16,
+    pub body: String,
+}
+
+impl Response {
+    pub fn is_success(&self) -> bool {
+        (200..300).contains(&self.status)
+    }
+
+    // ureq reports error statuses as errors, but callers decide what an error page means
+    fn read(result: Result<ureq::Response, ureq::Error>) -> io::Result<Self> {
+        let response = match result {
+            Ok(response) => response,
+            Err(ureq::Error::Status(_, response)) => response,
+            Err(ureq::Error::Transport(error)) => return Err(io::Error::other(error.to_string())),
+        };
+        Ok(Response {
+            status: response.status(),
+            body: response.into_string()?,
+        })
+    }
+}
+
+fn request(method: &str, url: &str, headers: &[(&str, &str)]) -> ureq::Request {
+    headers
+        .iter()
+        .fold(ureq::request(method, url), |request, (name, value)| {
+            request.set(name, value)
+        })
+}
+
+pub fn get(url: &str, headers: &[(&str, &str)]) -> io::Result<Response> {
+    Response::read(request("GET", url, headers).call())
+}
+
+pub fn post_form(
+    url: &str,
+    headers: &[(&str, &str)],
+    form: &[(&str, &str)],
+) -> io::Result<Response> {
+    Response::read(request("POST", url, headers).send_form(form))
+}
+
+// A stand-in for the site in tests, answering a single request with canned HTML
+#[cfg(test)]
+pub mod stub {
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+    use std::thread::{self, JoinHandle};
+
+    // Serves the status and body once, handing back the base URL and the request as received
+    pub fn serve_once(status: &str, body: &str) -> (String, JoinHandle<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let response = format!(
+            "HTTP/1.1 {status}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+            body.len()
+        );
+        let handle = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut request = String::new();
+            let mut content_length = 0;
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if line.trim_end().is_empty() {
+                    break;
+                }
+                if let Some((name, value)) = line.split_once(':') {
+                    if name.eq_ignore_ascii_case("content-length") {
+                        content_length = value.trim().parse().unwrap();
+                    }
+                }
+                request.push_str(&line);
+            }
+            let mut form = vec![0; content_length];
+            reader.read_exact(&mut form).unwrap();
+            request.push_str(&String::from_utf8(form).unwrap());
+            stream.write_all(response.as_bytes()).unwrap();
+            request
+        });
+        (base_url, handle)
+    }
+}
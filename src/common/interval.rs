@@ -0,0 +1,353 @@
+use std::ops::Range;
+
+// The integers start..end, half-open so that end is not included; empty when end <= start
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Interval {
+    pub start: i64,
+    pub end: i64,
+}
+
+impl Interval {
+    pub fn new(start: i64, end: i64) -> Self {
+        Interval { start, end }
+    }
+
+    pub fn with_len(start: i64, len: i64) -> Self {
+        Interval::new(start, start + len)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.end <= self.start
+    }
+
+    #[allow(dead_code)]
+    pub fn len(&self) -> u64 {
+        if self.is_empty() {
+            0
+        } else {
+            self.start.abs_diff(self.end)
+        }
+    }
+
+    pub fn contains(&self, x: i64) -> bool {
+        self.start <= x && x < self.end
+    }
+
+    // The overlap of the two intervals, or None if they do not overlap
+    pub fn intersection(&self, other: &Interval) -> Option<Interval> {
+        let overlap = Interval::new(self.start.max(other.start), self.end.min(other.end));
+        (!overlap.is_empty()).then_some(overlap)
+    }
+
+    pub fn shift(&self, by: i64) -> Interval {
+        Interval::new(self.start + by, self.end + by)
+    }
+
+    // The pieces either side of every cut point that falls inside the interval, in order.
+    // Each cut starts a new piece, so the pieces together cover the interval exactly.
+    pub fn split_at(&self, cuts: impl IntoIterator<Item = i64>) -> Vec<Interval> {
+        let mut cuts: Vec<_> = cuts
+            .into_iter()
+            .filter(|&cut| self.start < cut && cut < self.end)
+            .collect();
+        cuts.sort();
+        cuts.dedup();
+        let mut pieces = vec![];
+        let mut start = self.start;
+        for cut in cuts {
+            pieces.push(Interval::new(start, cut));
+            start = cut;
+        }
+        if start < self.end {
+            pieces.push(Interval::new(start, self.end));
+        }
+        pieces
+    }
+
+    #[allow(dead_code)]
+    pub fn iter(&self) -> Range<i64> {
+        self.start..self.end
+    }
+}
+
+impl From<Range<i64>> for Interval {
+    fn from(range: Range<i64>) -> Self {
+        Interval::new(range.start, range.end)
+    }
+}
+
+// A set of integers, kept as sorted, disjoint, non-empty intervals with gaps between them
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
+pub struct IntervalSet {
+    intervals: Vec<Interval>,
+}
+
+impl IntervalSet {
+    // Sorts the intervals and merges any that overlap or touch
+    fn normalize(mut intervals: Vec<Interval>) -> Self {
+        intervals.retain(|interval| !interval.is_empty());
+        intervals.sort();
+        let mut merged: Vec<Interval> = Vec::with_capacity(intervals.len());
+        for interval in intervals {
+            match merged.last_mut() {
+                Some(last) if interval.start <= last.end => last.end = last.end.max(interval.end),
+                _ => merged.push(interval),
+            }
+        }
+        IntervalSet { intervals: merged }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Interval> {
+        self.intervals.iter()
+    }
+
+    #[allow(dead_code)]
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    // How many integers are in the set
+    #[allow(dead_code)]
+    pub fn total_len(&self) -> u64 {
+        self.intervals.iter().map(Interval::len).sum()
+    }
+
+    #[allow(dead_code)]
+    pub fn contains(&self, x: i64) -> bool {
+        // The only interval that could contain x is the last one starting at or before it
+        let i = self
+            .intervals
+            .partition_point(|interval| interval.start <= x);
+        i > 0 && self.intervals[i - 1].contains(x)
+    }
+
+    pub fn min(&self) -> Option<i64> {
+        self.intervals.first().map(|interval| interval.start)
+    }
+
+    #[allow(dead_code)]
+    pub fn max(&self) -> Option<i64> {
+        self.intervals.last().map(|interval| interval.end - 1)
+    }
+
+    #[allow(dead_code)]
+    pub fn insert(&mut self, interval: Interval) {
+        let mut intervals = std::mem::take(&mut self.intervals);
+        intervals.push(interval);
+        *self = Self::normalize(intervals);
+    }
+
+    pub fn union(&self, other: &IntervalSet) -> IntervalSet {
+        self.iter().chain(other.iter()).copied().collect()
+    }
+
+    pub fn intersection(&self, other: &IntervalSet) -> IntervalSet {
+        let (mut i, mut j) = (0, 0);
+        let mut overlaps = vec![];
+        while let (Some(a), Some(b)) = (self.intervals.get(i), other.intervals.get(j)) {
+            overlaps.extend(a.intersection(b));
+            // Whichever ends first cannot overlap anything further along the other
+            if a.end < b.end {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+        IntervalSet {
+            intervals: overlaps,
+        }
+    }
+
+    // Everything in this set that is not in the other
+    pub fn difference(&self, other: &IntervalSet) -> IntervalSet {
+        let mut remaining = vec![];
+        let mut j = 0;
+        for interval in &self.intervals {
+            // Skip past everything in the other set that ends before this interval starts
+            while other
+                .intervals
+                .get(j)
+                .is_some_and(|b| b.end <= interval.start)
+            {
+                j += 1;
+            }
+            let mut start = interval.start;
+            for b in other.intervals[j..]
+                .iter()
+                .take_while(|b| b.start < interval.end)
+            {
+                if start < b.start {
+                    remaining.push(Interval::new(start, b.start));
+                }
+                start = start.max(b.end);
+            }
+            if start < interval.end {
+                remaining.push(Interval::new(start, interval.end));
+            }
+        }
+        IntervalSet {
+            intervals: remaining,
+        }
+    }
+
+    // Every interval split at the cut points falling inside it, see Interval::split_at
+    pub fn split_at(&self, cuts: &[i64]) -> Vec<Interval> {
+        self.iter()
+            .flat_map(|interval| interval.split_at(cuts.iter().copied()))
+            .collect()
+    }
+
+    pub fn shift(&self, by: i64) -> IntervalSet {
+        IntervalSet {
+            intervals: self.iter().map(|interval| interval.shift(by)).collect(),
+        }
+    }
+}
+
+impl FromIterator<Interval> for IntervalSet {
+    fn from_iter<I: IntoIterator<Item = Interval>>(iter: I) -> Self {
+        Self::normalize(iter.into_iter().collect())
+    }
+}
+
+impl From<Interval> for IntervalSet {
+    fn from(interval: Interval) -> Self {
+        Self::normalize(vec![interval])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use super::*;
+
+    fn set(intervals: &[(i64, i64)]) -> IntervalSet {
+        intervals
+            .iter()
+            .map(|&(start, end)| Interval::new(start, end))
+            .collect()
+    }
+
+    fn members(set: &IntervalSet) -> BTreeSet<i64> {
+        set.iter().flat_map(Interval::iter).collect()
+    }
+
+    // Small pseudo-random sets of intervals within -20..20, some empty and some touching
+    fn samples() -> Vec<Vec<(i64, i64)>> {
+        let mut state = 12345_u64;
+        let mut next = move |n: u64| {
+            state = state
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            (state >> 33) % n
+        };
+        (0..200)
+            .map(|_| {
+                (0..next(5))
+                    .map(|_| {
+                        let start = next(40) as i64 - 20;
+                        (start, start + next(12) as i64 - 2)
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn empty_intervals() {
+        assert!(Interval::new(3, 3).is_empty());
+        assert!(Interval::new(5, 2).is_empty());
+        assert_eq!(Interval::new(5, 2).len(), 0);
+        assert_eq!(Interval::with_len(-2, 4), Interval::new(-2, 2));
+        assert_eq!(Interval::new(-2, 2).len(), 4);
+        assert!(set(&[(3, 3), (5, 2)]).is_empty());
+        assert_eq!(IntervalSet::default().min(), None);
+        assert_eq!(IntervalSet::default().max(), None);
+    }
+
+    #[test]
+    fn contains_is_half_open() {
+        let interval = Interval::new(2, 5);
+        assert!(!interval.contains(1));
+        assert!(interval.contains(2));
+        assert!(interval.contains(4));
+        assert!(!interval.contains(5));
+    }
+
+    #[test]
+    fn touching_intervals_merge() {
+        assert_eq!(set(&[(3, 5), (0, 3)]), set(&[(0, 5)]));
+        assert_eq!(set(&[(0, 4), (2, 6), (6, 7)]), set(&[(0, 7)]));
+        // A gap of a single integer keeps them apart
+        assert_eq!(set(&[(0, 3), (4, 6)]).iter().count(), 2);
+        assert_eq!(Interval::new(0, 3).intersection(&Interval::new(3, 6)), None);
+    }
+
+    #[test]
+    fn split_ignores_cuts_on_or_outside_the_boundaries() {
+        let interval = Interval::new(0, 10);
+        assert_eq!(interval.split_at([0, 10, -5, 15]), vec![interval]);
+        assert_eq!(
+            interval.split_at([7, 3, 3, 10]),
+            vec![
+                Interval::new(0, 3),
+                Interval::new(3, 7),
+                Interval::new(7, 10)
+            ]
+        );
+        assert_eq!(Interval::new(4, 4).split_at([4]), vec![]);
+        assert_eq!(
+            set(&[(0, 4), (6, 10)]).split_at(&[4, 5, 8]),
+            vec![
+                Interval::new(0, 4),
+                Interval::new(6, 8),
+                Interval::new(8, 10)
+            ]
+        );
+    }
+
+    #[test]
+    fn matches_a_set_of_integers() {
+        let samples = samples();
+        for (a, b) in samples.iter().zip(samples.iter().rev()) {
+            let (set_a, set_b) = (set(a), set(b));
+            let (model_a, model_b) = (members(&set_a), members(&set_b));
+            let expected: BTreeSet<i64> = a.iter().flat_map(|&(s, e)| s..e).collect();
+            assert_eq!(model_a, expected, "{a:?}");
+
+            // Normalized: sorted, non-empty and with a gap between neighbours
+            for pair in set_a.intervals.windows(2) {
+                assert!(pair[0].end < pair[1].start, "{set_a:?}");
+            }
+            assert!(set_a.iter().all(|interval| !interval.is_empty()));
+
+            let context = format!("{a:?} and {b:?}");
+            let union: BTreeSet<_> = model_a.union(&model_b).copied().collect();
+            let intersection: BTreeSet<_> = model_a.intersection(&model_b).copied().collect();
+            let difference: BTreeSet<_> = model_a.difference(&model_b).copied().collect();
+            assert_eq!(members(&set_a.union(&set_b)), union, "{context}");
+            assert_eq!(
+                members(&set_a.intersection(&set_b)),
+                intersection,
+                "{context}"
+            );
+            assert_eq!(members(&set_a.difference(&set_b)), difference, "{context}");
+
+            assert_eq!(set_a.total_len(), model_a.len() as u64, "{context}");
+            assert_eq!(set_a.min(), model_a.first().copied(), "{context}");
+            assert_eq!(set_a.max(), model_a.last().copied(), "{context}");
+            for x in -25..35 {
+                assert_eq!(set_a.contains(x), model_a.contains(&x), "{x} in {a:?}");
+            }
+            let shifted: BTreeSet<_> = model_a.iter().map(|x| x - 7).collect();
+            assert_eq!(members(&set_a.shift(-7)), shifted, "{context}");
+
+            let mut inserted = set_a.clone();
+            for &(start, end) in b {
+                inserted.insert(Interval::new(start, end));
+            }
+            assert_eq!(inserted, set_a.union(&set_b), "{context}");
+        }
+    }
+}
@@ -1,69 +1,410 @@
 use aoc_parse::{parser, prelude::*};
 use colored::Colorize;
+use std::any::Any;
+use std::cell::Cell;
 use std::fmt::Display;
 use std::fs;
-use std::str::FromStr;
+use std::ops::RangeInclusive;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{mpsc, Arc};
+use std::thread;
+use std::time::Duration;
 
+use crate::common::answer::Answer;
+use crate::common::bench::{time, BenchReport, Stats};
+use crate::common::bounds::{Bounds, Contradiction};
+use crate::common::context::Context;
+use crate::common::examples::{self, Example};
+use crate::common::fetch::{self, FetchError};
+use crate::common::output::Record;
+use crate::common::problem::{AnyParsed, DynProblem, SolverError, SolverResult};
+use crate::common::puzzle::Puzzle;
 use crate::days;
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Part {
+    A,
+    B,
+}
+
+impl Display for Part {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Part::A => "A".fmt(f),
+            Part::B => "B".fmt(f),
+        }
+    }
+}
+
+// Which input a solution is run on: the real puzzle input, or an example with an optional label
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum InputKind {
+    Real,
+    Example(Option<String>),
+}
+
+impl InputKind {
+    pub fn is_example(&self) -> bool {
+        matches!(self, InputKind::Example(_))
+    }
+
+    pub fn label(&self) -> Option<&str> {
+        match self {
+            InputKind::Example(label) => label.as_deref(),
+            InputKind::Real => None,
+        }
+    }
+}
+
+// Which step of a run something went wrong in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Stage {
+    Parse,
+    Solve(Part),
+}
+
+impl Display for Stage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Stage::Parse => write!(f, "parse step"),
+            Stage::Solve(part) => write!(f, "part {part}"),
+        }
+    }
+}
+
+type PartResult = (Result<Option<Answer>, AocRunError>, Duration);
+// Parsed input, or None if parsing has not been written yet
+type ParseResult = (Result<Option<Arc<AnyParsed>>, AocRunError>, Duration);
+// Everything a part needs to be solved: the parsed input and its parameters
+type Prepared = (Arc<AnyParsed>, Arc<Context>);
+
 #[derive(Debug)]
 pub struct RunCode {
-    problem: u32,
+    problem: Puzzle,
     run_a: bool,
     run_b: bool,
-    solution_a: Option<String>,
-    solution_b: Option<String>,
-    as_example: bool,
+    solution_a: Option<Answer>,
+    solution_b: Option<Answer>,
+    error_a: Option<AocRunError>,
+    error_b: Option<AocRunError>,
+    time_parse: Option<Duration>,
+    time_a: Option<Duration>,
+    time_b: Option<Duration>,
+    timeout: Option<Duration>,
+    input: InputKind,
 }
 
 impl RunCode {
-    pub fn init_all(problem: u32) -> Self {
+    fn init(problem: Puzzle, input: InputKind, run_a: bool, run_b: bool) -> Self {
         Self {
             problem,
-            run_a: true,
-            run_b: true,
+            run_a,
+            run_b,
             solution_a: None,
             solution_b: None,
-            as_example: false,
+            error_a: None,
+            error_b: None,
+            time_parse: None,
+            time_a: None,
+            time_b: None,
+            timeout: None,
+            input,
         }
     }
-}
 
-impl FromStr for RunCode {
-    type Err = aoc_parse::ParseError;
+    pub fn init_all(problem: Puzzle) -> Self {
+        Self::init(problem, InputKind::Real, true, true)
+    }
+
+    // Runs the parts an example applies to
+    pub fn init_example(problem: Puzzle, example: &Example) -> Self {
+        let input = InputKind::Example(example.label.clone());
+        Self::init(problem, input, example.part_a, example.part_b)
+    }
+
+    pub fn with_timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    pub fn solution(&self, part: Part) -> Option<&Answer> {
+        match part {
+            Part::A => self.solution_a.as_ref(),
+            Part::B => self.solution_b.as_ref(),
+        }
+    }
+
+    pub fn error(&self, part: Part) -> Option<&AocRunError> {
+        match part {
+            Part::A => self.error_a.as_ref(),
+            Part::B => self.error_b.as_ref(),
+        }
+    }
+
+    pub fn time(&self, part: Part) -> Option<Duration> {
+        match part {
+            Part::A => self.time_a,
+            Part::B => self.time_b,
+        }
+    }
+
+    pub fn parse_time(&self) -> Option<Duration> {
+        self.time_parse
+    }
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
+    pub fn problem(&self) -> Puzzle {
+        self.problem
+    }
+
+    pub fn input(&self) -> &InputKind {
+        &self.input
+    }
+
+    pub fn runs(&self, part: Part) -> bool {
+        match part {
+            Part::A => self.run_a,
+            Part::B => self.run_b,
+        }
+    }
+}
+
+impl RunCode {
+    // Parses a comma separated list of run codes, where each item is one of
+    //   [~]N[a|b|ab]      a single day
+    //   [~]N-M[a|b|ab]    an inclusive range of days
+    //   ~N[a|b|ab]:LABEL  a single labelled example, data/examples/NN-LABEL.txt
+    //   all[~]            every registered day, of every year
+    // Days are in the configured year unless prefixed by another, as in 2022:5b or ~2022:1-3,
+    // and YEAR:all[~] is every registered day of that year.
+    // Without a label, ~ runs every example of the day on the parts it applies to.
+    // Days run from 1 to 25, and a range must not run backwards, as in 5-3.
+    pub fn parse_list(s: &str) -> Result<Vec<Self>, String> {
         let ab_parser = parser!({
             "a" => (true, false),
              "b" => (false, true),
              "ab" => (true, true),
              "" => (true, true)
         });
-        let example_parser = parser!({"~" => true, "" => false});
-        let code_parser = parser!(example_parser u32 ab_parser);
-        let (as_example, problem, (run_a, run_b)) = code_parser.parse(s)?;
-        Ok(RunCode {
-            problem,
-            run_a,
-            run_b,
-            solution_a: None,
-            solution_b: None,
-            as_example,
-        })
+        let year_parser = parser!({year:u32 ":" => Some(year), "" => None});
+        let range_parser = parser!({
+            start:u32 "-" end:u32 => start..=end,
+            day:u32 => day..=day,
+        });
+        let days = |year: Option<u32>, range: RangeInclusive<u32>| -> Result<Vec<Puzzle>, String> {
+            let (start, end) = (*range.start(), *range.end());
+            if start > end {
+                return Err(format!("the range {start}-{end} runs backwards"));
+            }
+            if let Some(day) = [start, end].into_iter().find(|day| !(1..=25).contains(day)) {
+                return Err(format!("there is no day {day}, days run from 1 to 25"));
+            }
+            Ok(range
+                .map(|day| match year {
+                    Some(year) => Puzzle::new(year, day),
+                    None => Puzzle::in_current_year(day),
+                })
+                .collect())
+        };
+        let registered = |year: Option<u32>| -> Result<Vec<Puzzle>, String> {
+            Ok(days::registered_days()
+                .into_iter()
+                .filter(|puzzle| year.is_none_or(|year| puzzle.year == year))
+                .collect())
+        };
+        let label_parser = parser!({":" label:string(alnum+) => Some(label), "" => None});
+        let item_parser = parser!({
+            year:year_parser "all~" => (Some(None), registered(year), (true, true)),
+            year:year_parser "all" => (None, registered(year), (true, true)),
+            "~" year:year_parser range:range_parser ab:ab_parser label:label_parser => (Some(label), days(year, range), ab),
+            year:year_parser range:range_parser ab:ab_parser => (None, days(year, range), ab),
+        });
+        let list_parser = parser!(repeat_sep(item_parser, ","));
+        let mut codes = vec![];
+        for (examples, problems, (run_a, run_b)) in
+            list_parser.parse(s).map_err(|e| e.to_string())?
+        {
+            for problem in problems? {
+                match &examples {
+                    None => codes.push(RunCode::init(problem, InputKind::Real, run_a, run_b)),
+                    Some(label) => codes.extend(Self::example_codes(problem, label, run_a, run_b)),
+                }
+            }
+        }
+        Ok(codes)
+    }
+
+    // Restricts the requested parts to those each example applies to. An explicitly labelled
+    // example that applies to none of them is run anyway, since that is what was asked for.
+    fn example_codes(
+        problem: Puzzle,
+        label: &Option<String>,
+        run_a: bool,
+        run_b: bool,
+    ) -> Vec<Self> {
+        let restrict = |example: &Example| {
+            let input = InputKind::Example(example.label.clone());
+            Self::init(
+                problem,
+                input,
+                run_a && example.part_a,
+                run_b && example.part_b,
+            )
+        };
+        if label.is_some() {
+            let code = restrict(&Example::load(problem, label.clone()));
+            return if code.run_a || code.run_b {
+                vec![code]
+            } else {
+                vec![Self::init(
+                    problem,
+                    InputKind::Example(label.clone()),
+                    run_a,
+                    run_b,
+                )]
+            };
+        }
+        let codes: Vec<_> = examples::list(problem)
+            .iter()
+            .map(restrict)
+            .filter(|code| code.run_a || code.run_b)
+            .collect();
+        if codes.is_empty() {
+            // Reported as a missing example when run
+            vec![Self::init(problem, InputKind::Example(None), run_a, run_b)]
+        } else {
+            codes
+        }
     }
 }
 
-fn print_solution(prefix: &str, solution: &Option<String>) {
+// A warning, such as an answer contradicting earlier submissions, is printed below the solution
+fn print_solution(
+    prefix: &str,
+    solution: &Option<Answer>,
+    error: &Option<AocRunError>,
+    warning: Option<AocRunError>,
+) {
+    if let Some(e) = error {
+        println!("{prefix} : {e}");
+        return;
+    }
     let solution_str = match solution {
         Some(s) => s.to_string().bold().cyan(),
         None => "TODO".to_string().dimmed(),
     };
-    println!("{prefix} : {solution_str}")
+    println!("{prefix} : {solution_str}");
+    if let Some(warning) = warning {
+        println!("{} {warning}", "  WARNING:".bold().red().reversed());
+    }
+}
+
+thread_local! {
+    // Set while a stage runs under RunCode::isolate, which reports its panics itself
+    static ISOLATED: Cell<bool> = const { Cell::new(false) };
+}
+
+// How many stages are running under RunCode::isolate, on any thread
+static ISOLATED_STAGES: AtomicUsize = AtomicUsize::new(0);
+
+// Whether a panic here is caught by RunCode::isolate. Parts that use rayon run closures on the
+// pool's threads, whose panics are rethrown into the stage, so those threads count as isolated
+// while any stage runs.
+fn is_isolated() -> bool {
+    ISOLATED.get()
+        || (rayon::current_thread_index().is_some() && ISOLATED_STAGES.load(Ordering::SeqCst) > 0)
+}
+
+// Panics within a day are reported against that day and part, so the default hook's message and
+// backtrace are kept for panics anywhere else
+pub fn install_panic_hook() {
+    let default_hook = panic::take_hook();
+    panic::set_hook(Box::new(move |info| {
+        if !is_isolated() {
+            default_hook(info)
+        }
+    }));
+}
+
+fn catch_panic<T>(f: impl FnOnce() -> T) -> thread::Result<T> {
+    let was_isolated = ISOLATED.replace(true);
+    ISOLATED_STAGES.fetch_add(1, Ordering::SeqCst);
+    let result = panic::catch_unwind(AssertUnwindSafe(f));
+    ISOLATED_STAGES.fetch_sub(1, Ordering::SeqCst);
+    ISOLATED.set(was_isolated);
+    result
 }
 
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    if let Some(s) = payload.downcast_ref::<&str>() {
+        s.to_string()
+    } else if let Some(s) = payload.downcast_ref::<String>() {
+        s.clone()
+    } else {
+        "unknown panic payload".to_string()
+    }
+}
+
+#[derive(Debug, Clone)]
 pub enum AocRunError {
     NoFile(String),
-    UnregistedProblem(u32),
+    UnregistedProblem(Puzzle),
+    Params {
+        path: String,
+        error: aoc_parse::ParseError,
+    },
+    Fetch {
+        problem: Puzzle,
+        error: FetchError,
+    },
+    Panic {
+        problem: Puzzle,
+        stage: Stage,
+        message: String,
+    },
+    Timeout {
+        problem: Puzzle,
+        stage: Stage,
+        limit: Duration,
+    },
+    Solver {
+        problem: Puzzle,
+        stage: Stage,
+        error: SolverError,
+    },
+    ContradictsBounds {
+        problem: Puzzle,
+        part: Part,
+        answer: Answer,
+        contradiction: Contradiction,
+    },
+}
+
+impl AocRunError {
+    pub fn kind(&self) -> &'static str {
+        match self {
+            AocRunError::NoFile(_) => "NoFile",
+            AocRunError::UnregistedProblem(_) => "UnregistedProblem",
+            AocRunError::Params { .. } => "Params",
+            AocRunError::Fetch { error, .. } => error.kind(),
+            AocRunError::Panic { .. } => "Panic",
+            AocRunError::Timeout { .. } => "Timeout",
+            AocRunError::Solver { error, .. } => error.kind(),
+            AocRunError::ContradictsBounds { .. } => "ContradictsBounds",
+        }
+    }
+
+    // Line and column of the input where parsing failed, if that is what went wrong
+    pub fn parse_location(&self) -> Option<(usize, usize)> {
+        match self {
+            AocRunError::Solver {
+                error: SolverError::Parse { line, column, .. },
+                ..
+            } => Some((*line, *column)),
+            _ => None,
+        }
+    }
 }
 
 impl Display for AocRunError {
@@ -73,41 +414,396 @@ impl Display for AocRunError {
             AocRunError::UnregistedProblem(problem) => {
                 format!("No struct registered for problem {problem}")
             }
+            AocRunError::Params { path, error } => {
+                format!("Could not read parameters at {path}: {error}")
+            }
+            AocRunError::Fetch { problem, error } => {
+                format!("Could not fetch input for problem {problem}: {error}")
+            }
+            AocRunError::Panic {
+                problem,
+                stage,
+                message,
+            } => format!("Problem {problem} {stage} panicked: {message}"),
+            AocRunError::Timeout {
+                problem,
+                stage,
+                limit,
+            } => format!("Problem {problem} {stage} timed out after {limit:?}"),
+            AocRunError::Solver {
+                problem,
+                stage,
+                error,
+            } => format!("Problem {problem} {stage} failed: {error}"),
+            AocRunError::ContradictsBounds {
+                problem,
+                part,
+                answer,
+                contradiction,
+            } => format!("Problem {problem} part {part} answer {answer} {contradiction}"),
         };
         explanation.bold().red().fmt(f)
     }
 }
 
 impl RunCode {
+    fn input_path(&self) -> String {
+        match &self.input {
+            InputKind::Example(label) => examples::path(self.problem, label),
+            InputKind::Real => fetch::input_path(self.problem),
+        }
+    }
+
+    // Missing real inputs are downloaded first, if there is a session to download them with
     fn get_input(&self) -> Result<String, AocRunError> {
-        let foldername = if self.as_example {
-            "examples"
-        } else {
-            "inputs"
-        };
         let problem = self.problem;
-        let path = format!("./data/{foldername}/{problem:02}.txt");
+        let path = self.input_path();
+        if self.input == InputKind::Real && !Path::new(&path).exists() && fetch::session().is_some()
+        {
+            fetch::fetch_input(problem).map_err(|error| AocRunError::Fetch { problem, error })?;
+        }
         fs::read_to_string(&path).map_err(|_e| AocRunError::NoFile(path))
     }
 
-    pub fn run(mut self) -> Result<Self, AocRunError> {
+    // Parameters for this input, from the .params sidecar next to it
+    fn get_context(&self) -> Result<Context, AocRunError> {
+        let input_path = self.input_path();
+        let path = input_path
+            .strip_suffix(".txt")
+            .unwrap_or(&input_path)
+            .to_string()
+            + ".params";
+        Context::load(&path).map_err(|error| AocRunError::Params { path, error })
+    }
+
+    // Runs one stage, catching any panic so that one broken day cannot abort the whole run.
+    // With a timeout the stage runs on its own worker thread, which is abandoned if it overruns.
+    // Threads cannot be killed, so the flag handed to the stage is raised instead, and a part that
+    // polls it through Context::check_cancelled stops; one that does not runs on in the background.
+    fn isolate<T: Send + 'static>(
+        &self,
+        stage: Stage,
+        f: impl FnOnce(Arc<AtomicBool>) -> T + Send + 'static,
+    ) -> (Result<T, AocRunError>, Duration) {
+        let panic_error = |payload| AocRunError::Panic {
+            problem: self.problem,
+            stage,
+            message: panic_message(payload),
+        };
+        let cancelled = Arc::new(AtomicBool::new(false));
+        time(|| match self.timeout {
+            None => catch_panic(|| f(cancelled)).map_err(panic_error),
+            Some(limit) => {
+                let (sender, receiver) = mpsc::channel();
+                let flag = Arc::clone(&cancelled);
+                thread::spawn(move || {
+                    let _ = sender.send(catch_panic(|| f(flag)));
+                });
+                match receiver.recv_timeout(limit) {
+                    Ok(result) => result.map_err(panic_error),
+                    Err(_) => {
+                        cancelled.store(true, Ordering::Relaxed);
+                        Err(AocRunError::Timeout {
+                            problem: self.problem,
+                            stage,
+                            limit,
+                        })
+                    }
+                }
+            }
+        })
+    }
+
+    // Unimplemented stages are reported as TODO rather than as errors
+    fn solver_result<T>(
+        &self,
+        stage: Stage,
+        result: Result<T, SolverError>,
+    ) -> Result<Option<T>, AocRunError> {
+        match result {
+            Ok(output) => Ok(Some(output)),
+            Err(SolverError::NotImplemented) => Ok(None),
+            Err(error) => Err(AocRunError::Solver {
+                problem: self.problem,
+                stage,
+                error,
+            }),
+        }
+    }
+
+    fn parse_input(&self, problem: Box<dyn DynProblem>, input: &Arc<str>) -> ParseResult {
+        let input = Arc::clone(input);
+        let (result, elapsed) = self.isolate(Stage::Parse, move |_cancelled| {
+            problem.parse(&input).map(Arc::<AnyParsed>::from)
+        });
+        let result = result.and_then(|parsed| self.solver_result(Stage::Parse, parsed));
+        (result, elapsed)
+    }
+
+    fn solve_part(&self, part: Part, (parsed, ctx): &Prepared) -> PartResult {
+        let problem = match days::get_problem(self.problem) {
+            Ok(problem) => problem,
+            Err(e) => return (Err(e), Duration::ZERO),
+        };
+        let parsed = Arc::clone(parsed);
+        let ctx = Arc::clone(ctx);
+        let stage = Stage::Solve(part);
+        let (result, elapsed) = self.isolate(stage, move |cancelled| {
+            let ctx = ctx.with_cancel_flag(cancelled);
+            match part {
+                Part::A => problem.solve_a(&*parsed, &ctx),
+                Part::B => problem.solve_b(&*parsed, &ctx),
+            }
+        });
+        let result = result.and_then(|solution| self.solver_result(stage, solution));
+        (result, elapsed)
+    }
+
+    // Parses the input and loads its parameters once, ready to be shared by both parts.
+    // Returns None if there is nothing left to solve, because parsing failed or is still TODO.
+    fn parse_once(&mut self) -> Result<Option<Prepared>, AocRunError> {
+        let problem = days::get_problem(self.problem)?;
+        let input: Arc<str> = self.get_input()?.into();
+        let ctx = Arc::new(self.get_context()?);
+        let (result, elapsed) = self.parse_input(problem, &input);
+        self.time_parse = Some(elapsed);
+        match result {
+            Ok(parsed) => Ok(parsed.map(|parsed| (parsed, ctx))),
+            Err(e) => {
+                if self.run_a {
+                    self.error_a = Some(e.clone());
+                }
+                if self.run_b {
+                    self.error_b = Some(e);
+                }
+                Ok(None)
+            }
+        }
+    }
+
+    pub fn run(&mut self) -> Result<(), AocRunError> {
+        let Some(prepared) = self.parse_once()? else {
+            return Ok(());
+        };
+        let result_a = self.run_a.then(|| self.solve_part(Part::A, &prepared));
+        let result_b = self.run_b.then(|| self.solve_part(Part::B, &prepared));
+        self.store_results(result_a, result_b);
+        Ok(())
+    }
+
+    // As with run but solves both parts concurrently
+    pub fn run_parallel(&mut self) -> Result<(), AocRunError> {
+        let Some(prepared) = self.parse_once()? else {
+            return Ok(());
+        };
+        let (result_a, result_b) = rayon::join(
+            || self.run_a.then(|| self.solve_part(Part::A, &prepared)),
+            || self.run_b.then(|| self.solve_part(Part::B, &prepared)),
+        );
+        self.store_results(result_a, result_b);
+        Ok(())
+    }
+
+    fn store_results(&mut self, result_a: Option<PartResult>, result_b: Option<PartResult>) {
+        if let Some((result, elapsed)) = result_a {
+            match result {
+                Ok(solution) => self.solution_a = solution,
+                Err(e) => self.error_a = Some(e),
+            }
+            self.time_a = Some(elapsed);
+        }
+        if let Some((result, elapsed)) = result_b {
+            match result {
+                Ok(solution) => self.solution_b = solution,
+                Err(e) => self.error_b = Some(e),
+            }
+            self.time_b = Some(elapsed);
+        }
+    }
+
+    // Total time spent parsing and solving parts, regardless of whether they ran concurrently
+    pub fn solve_time(&self) -> Duration {
+        self.time_parse.unwrap_or_default()
+            + self.time_a.unwrap_or_default()
+            + self.time_b.unwrap_or_default()
+    }
+
+    pub fn bench(&self, runs: usize) -> Result<BenchReport, AocRunError> {
         let problem = days::get_problem(self.problem)?;
+        // Warm up the file cache and each part before timing anything
         let input = self.get_input()?;
-        if self.run_a {
-            self.solution_a = problem.solve_a(&input);
+        let ctx = self.get_context()?;
+        let load_samples = (0..runs)
+            .map(|_| time(|| self.get_input()).1)
+            .collect::<Vec<_>>();
+        let parsed = problem.parse(&input);
+        let parse_samples = (0..runs)
+            .map(|_| time(|| problem.parse(&input)).1)
+            .collect::<Vec<_>>();
+        // Parts can only be benchmarked once parsing has been written
+        let parsed = self.solver_result(Stage::Parse, parsed)?;
+        let bench_part = |solve: &dyn Fn(&AnyParsed) -> SolverResult| {
+            let parsed = parsed.as_deref()?;
+            let _ = solve(parsed);
+            let samples: Vec<_> = (0..runs).map(|_| time(|| solve(parsed)).1).collect();
+            Some(Stats::from_samples(&samples))
+        };
+        let part_a = self
+            .run_a
+            .then(|| bench_part(&|parsed| problem.solve_a(parsed, &ctx)))
+            .flatten();
+        let part_b = self
+            .run_b
+            .then(|| bench_part(&|parsed| problem.solve_b(parsed, &ctx)))
+            .flatten();
+        Ok(BenchReport {
+            load: Stats::from_samples(&load_samples),
+            parse: Stats::from_samples(&parse_samples),
+            part_a,
+            part_b,
+        })
+    }
+
+    // Whether every part that was run produced an answer
+    pub fn is_solved(&self) -> bool {
+        (!self.run_a || self.solution_a.is_some()) && (!self.run_b || self.solution_b.is_some())
+    }
+
+    pub fn print_header(&self) {
+        match &self.input {
+            InputKind::Real => println!("Day {}", self.problem),
+            InputKind::Example(None) => println!("Day {} (example)", self.problem),
+            InputKind::Example(Some(label)) => println!("Day {} (example {label})", self.problem),
         }
-        if self.run_b {
-            self.solution_b = problem.solve_b(&input);
+    }
+
+    // One record per requested part, each carrying the error if the run failed
+    pub fn records(&self, error: Option<&AocRunError>) -> Vec<Record> {
+        let parts = [
+            (
+                self.run_a,
+                Part::A,
+                &self.solution_a,
+                &self.error_a,
+                self.time_a,
+            ),
+            (
+                self.run_b,
+                Part::B,
+                &self.solution_b,
+                &self.error_b,
+                self.time_b,
+            ),
+        ];
+        parts
+            .into_iter()
+            .filter(|(run, ..)| *run)
+            .map(|(_, part, solution, part_error, time)| Record {
+                problem: self.problem,
+                part,
+                input: self.input.clone(),
+                answer: solution.clone(),
+                error: error.or(part_error.as_ref()).cloned(),
+                parse_time: self.time_parse,
+                time,
+            })
+            .collect()
+    }
+
+    // Whether the solution to a part is already known to be wrong from previous submissions.
+    // Only the real input is ever submitted, so example answers are never contradicted.
+    pub fn bounds_error(&self, part: Part) -> Option<AocRunError> {
+        if self.input.is_example() {
+            return None;
         }
-        Ok(self)
+        let answer = self.solution(part)?;
+        let contradiction = Bounds::load(self.problem, part).contradiction(answer)?;
+        Some(AocRunError::ContradictsBounds {
+            problem: self.problem,
+            part,
+            answer: answer.clone(),
+            contradiction,
+        })
     }
 
     pub fn print(&self) {
         if self.run_a {
-            print_solution("A", &self.solution_a);
+            let warning = self.bounds_error(Part::A);
+            print_solution("A", &self.solution_a, &self.error_a, warning);
         }
         if self.run_b {
-            print_solution("B", &self.solution_b);
+            let warning = self.bounds_error(Part::B);
+            print_solution("B", &self.solution_b, &self.error_b, warning);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rayon::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn timed_out_parts_are_cancelled() {
+        let rc =
+            RunCode::init_all(Puzzle::new(2023, 1)).with_timeout(Some(Duration::from_millis(20)));
+        let (stopped, stopped_receiver) = mpsc::channel();
+        let (result, _) = rc.isolate(Stage::Solve(Part::A), move |cancelled| {
+            let ctx = Context::default().with_cancel_flag(cancelled);
+            // Spins until cancelled, as a part on an endless input would
+            let result = loop {
+                if let Err(e) = ctx.check_cancelled() {
+                    break e;
+                }
+                thread::yield_now();
+            };
+            let _ = stopped.send(());
+            result
+        });
+        assert!(
+            matches!(result, Err(AocRunError::Timeout { .. })),
+            "{result:?}"
+        );
+        assert!(
+            stopped_receiver
+                .recv_timeout(Duration::from_secs(5))
+                .is_ok(),
+            "the worker should stop once cancelled"
+        );
+    }
+
+    #[test]
+    fn parts_within_the_limit_are_not_cancelled() {
+        let rc = RunCode::init_all(Puzzle::new(2023, 1)).with_timeout(Some(Duration::from_secs(5)));
+        let (result, _) = rc.isolate(Stage::Solve(Part::A), move |cancelled| {
+            Context::default()
+                .with_cancel_flag(cancelled)
+                .check_cancelled()
+        });
+        assert!(matches!(result, Ok(Ok(()))), "{result:?}");
+    }
+
+    #[test]
+    fn ranges_run_forwards_within_the_event() {
+        let days: Vec<_> = RunCode::parse_list("2022:3-5a")
+            .unwrap()
+            .iter()
+            .map(RunCode::problem)
+            .collect();
+        let expected: Vec<_> = (3..=5).map(|day| Puzzle::new(2022, day)).collect();
+        assert_eq!(days, expected);
+        assert_eq!(RunCode::parse_list("4-4").unwrap().len(), 1);
+        assert!(RunCode::parse_list("5-3").is_err());
+        assert!(RunCode::parse_list("1,~2022:9-1b").is_err());
+        for outside in ["0", "26", "~26b", "2022:0-3", "24-26"] {
+            assert!(RunCode::parse_list(outside).is_err(), "{outside}");
         }
     }
+
+    #[test]
+    fn rayon_closures_within_a_stage_are_isolated() {
+        let result = catch_panic(|| (0..8).into_par_iter().all(|_| is_isolated()));
+        assert!(matches!(result, Ok(true)));
+    }
 }
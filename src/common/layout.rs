@@ -0,0 +1,54 @@
+// Where the project keeps its solutions and data. build.rs includes this file too, so that the
+// days it registers and the example tests it generates agree with what the runner reads, which
+// means it can only use std.
+use std::path::{Path, PathBuf};
+
+// The event this workspace started with, which bare day numbers refer to unless configured
+// otherwise. Its data may still be in the flat layout from before multi-year support, and its
+// solutions are the ones directly in src/days.
+pub const DEFAULT_YEAR: u32 = 2023;
+
+// The project root, the directory holding the config file or else the current directory
+pub fn project_root(config_file: Option<&Path>) -> PathBuf {
+    match config_file.and_then(Path::parent) {
+        Some(dir) if !dir.as_os_str().is_empty() => dir.to_path_buf(),
+        _ => PathBuf::from("."),
+    }
+}
+
+// The configured data directory relative to the project root, or else data/
+pub fn data_root(root: &Path, data: Option<PathBuf>) -> PathBuf {
+    root.join(data.unwrap_or("data".into()))
+}
+
+// Directory holding one kind of data, e.g. inputs, for a year: <data root>/<year>/<kind>.
+// The default year falls back to the flat <data root>/<kind> while <year>/<kind> does not exist.
+pub fn data_dir(data_root: &Path, year: u32, kind: &str) -> PathBuf {
+    let dir = data_root.join(year.to_string()).join(kind);
+    let flat = data_root.join(kind);
+    if year == DEFAULT_YEAR && !dir.exists() && flat.exists() {
+        flat
+    } else {
+        dir
+    }
+}
+
+// A day's solution, src/days/dayNN.rs for the default year or src/days/<year>/dayNN.rs
+pub fn source_path(year: u32, day: u32) -> String {
+    if year == DEFAULT_YEAR {
+        format!("src/days/day{day:02}.rs")
+    } else {
+        format!("src/days/{year}/day{day:02}.rs")
+    }
+}
+
+// Module names for the default year stay dayNN, other years are prefixed like y2022_dayNN.
+// Only build.rs declares the modules.
+#[allow(dead_code)]
+pub fn module_name(year: u32, day: u32) -> String {
+    if year == DEFAULT_YEAR {
+        format!("day{day:02}")
+    } else {
+        format!("y{year}_day{day:02}")
+    }
+}
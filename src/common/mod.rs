@@ -1,2 +1,23 @@
+pub mod answer;
+pub mod answers;
+pub mod bench;
+pub mod bounds;
+pub mod check;
+pub mod config;
+pub mod context;
+#[cfg(test)]
+mod example_tests;
+pub mod examples;
+pub mod fetch;
+pub mod geom;
+pub mod grid;
+pub mod http;
+pub mod interval;
 pub mod io;
+pub mod layout;
+pub mod output;
 pub mod problem;
+pub mod puzzle;
+pub mod scaffold;
+pub mod submit;
+pub mod watch;
@@ -0,0 +1,173 @@
+use std::fmt::Display;
+use std::str::FromStr;
+use std::time::Duration;
+
+use crate::common::answer::Answer;
+use crate::common::io::{AocRunError, InputKind, Part};
+use crate::common::puzzle::Puzzle;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Text,
+    Json,
+    Csv,
+}
+
+impl Display for Format {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Format::Text => "text".fmt(f),
+            Format::Json => "json".fmt(f),
+            Format::Csv => "csv".fmt(f),
+        }
+    }
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            "csv" => Ok(Format::Csv),
+            _ => Err(format!(
+                "Unknown format {s}, expected one of text, json or csv"
+            )),
+        }
+    }
+}
+
+// The outcome of running a single part, flattened for machine-readable output
+pub struct Record {
+    pub problem: Puzzle,
+    pub part: Part,
+    pub input: InputKind,
+    pub answer: Option<Answer>,
+    pub error: Option<AocRunError>,
+    // Parsing is shared by both parts of a day, so each of their records carries the same parse time
+    pub parse_time: Option<Duration>,
+    pub time: Option<Duration>,
+}
+
+fn json_string(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len() + 2);
+    escaped.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if c.is_control() => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped.push('"');
+    escaped
+}
+
+fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
+fn time_ms(time: Option<Duration>) -> Option<String> {
+    time.map(|t| format!("{:.3}", t.as_secs_f64() * 1000.0))
+}
+
+impl Record {
+    fn to_json(&self) -> String {
+        // Numbers are emitted as JSON numbers, even if too large for some consumers to represent exactly
+        let answer = self.answer.as_ref().map_or("null".to_string(), |a| {
+            if a.is_number() {
+                a.to_string()
+            } else {
+                json_string(&a.to_string())
+            }
+        });
+        let error = self.error.as_ref().map_or("null".to_string(), |e| {
+            let location = e.parse_location().map_or(String::new(), |(line, column)| {
+                format!(", \"line\": {line}, \"column\": {column}")
+            });
+            format!(
+                "{{\"kind\": {}, \"message\": {}{location}}}",
+                json_string(e.kind()),
+                json_string(&e.to_string())
+            )
+        });
+        let label = self.input.label().map_or("null".to_string(), json_string);
+        let parse_time = time_ms(self.parse_time).unwrap_or("null".to_string());
+        let time = time_ms(self.time).unwrap_or("null".to_string());
+        format!(
+            "{{\"year\": {}, \"day\": {}, \"part\": {}, \"example\": {}, \"label\": {label}, \"answer\": {answer}, \"error\": {error}, \"parse_ms\": {parse_time}, \"time_ms\": {time}}}",
+            self.problem.year,
+            self.problem.day,
+            json_string(&self.part.to_string().to_lowercase()),
+            self.input.is_example(),
+        )
+    }
+
+    fn to_csv(&self) -> String {
+        let fields = [
+            self.problem.year.to_string(),
+            self.problem.day.to_string(),
+            self.part.to_string().to_lowercase(),
+            self.input.is_example().to_string(),
+            self.input.label().unwrap_or_default().to_string(),
+            self.answer
+                .as_ref()
+                .map(|a| a.to_string())
+                .unwrap_or_default(),
+            self.error
+                .as_ref()
+                .map(|e| e.kind())
+                .unwrap_or_default()
+                .to_string(),
+            self.error
+                .as_ref()
+                .map(|e| e.to_string())
+                .unwrap_or_default(),
+            time_ms(self.parse_time).unwrap_or_default(),
+            time_ms(self.time).unwrap_or_default(),
+        ];
+        fields
+            .iter()
+            .map(|f| csv_field(f))
+            .collect::<Vec<_>>()
+            .join(",")
+    }
+}
+
+pub fn print_summary(records: &[Record]) {
+    let count = |pred: fn(&Record) -> bool| records.iter().filter(|r| pred(r)).count();
+    let succeeded = count(|r| r.error.is_none() && r.answer.is_some());
+    let todo = count(|r| r.error.is_none() && r.answer.is_none());
+    let panicked = count(|r| matches!(r.error, Some(AocRunError::Panic { .. })));
+    let errored = records.len() - succeeded - todo - panicked;
+    println!("{succeeded} succeeded, {todo} TODO, {errored} errored, {panicked} panicked");
+}
+
+pub fn print_records(format: Format, records: &[Record]) {
+    match format {
+        Format::Json if records.is_empty() => println!("[]"),
+        Format::Json => {
+            let rows: Vec<_> = records
+                .iter()
+                .map(|r| format!("  {}", r.to_json()))
+                .collect();
+            println!("[\n{}\n]", rows.join(",\n"));
+        }
+        Format::Csv => {
+            println!("year,day,part,example,label,answer,error,error_message,parse_ms,time_ms");
+            for record in records {
+                println!("{}", record.to_csv());
+            }
+        }
+        Format::Text => unreachable!("Text output is printed directly by RunCode"),
+    }
+}
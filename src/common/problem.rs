@@ -1,4 +1,97 @@
-pub trait Problem {
-    fn solve_a(&self, input: &str) -> Option<String>;
-    fn solve_b(&self, input: &str) -> Option<String>;
+use std::any::Any;
+use std::fmt::Display;
+
+use crate::common::answer::Answer;
+use crate::common::context::Context;
+
+#[derive(Debug, Clone)]
+pub enum SolverError {
+    // Returned by parts that have not been written yet, see __template.rs
+    #[allow(dead_code)]
+    NotImplemented,
+    Parse {
+        line: usize,
+        column: usize,
+        error: aoc_parse::ParseError,
+    },
+    InvalidInput(String),
+    // Returned by parts that stop early after timing out, see Context::check_cancelled
+    Cancelled,
+}
+
+impl SolverError {
+    pub fn kind(&self) -> &'static str {
+        match self {
+            SolverError::NotImplemented => "NotImplemented",
+            SolverError::Parse { .. } => "Parse",
+            SolverError::InvalidInput(_) => "InvalidInput",
+            SolverError::Cancelled => "Cancelled",
+        }
+    }
+}
+
+impl From<aoc_parse::ParseError> for SolverError {
+    fn from(error: aoc_parse::ParseError) -> Self {
+        let before = &error.source[..error.location];
+        let line = before.matches('\n').count() + 1;
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+        let column = before[line_start..].chars().count() + 1;
+        SolverError::Parse {
+            line,
+            column,
+            error,
+        }
+    }
+}
+
+impl Display for SolverError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SolverError::NotImplemented => write!(f, "not implemented"),
+            SolverError::Parse { error, .. } => write!(f, "could not parse input: {error}"),
+            SolverError::InvalidInput(message) => write!(f, "invalid input: {message}"),
+            SolverError::Cancelled => write!(f, "cancelled after running out of time"),
+        }
+    }
+}
+
+pub type SolverResult = Result<Answer, SolverError>;
+
+// Each problem parses its input once, into its own Parsed type, which is then shared by both parts.
+// The Context carries any parameters that differ between inputs, such as between example and real.
+pub trait Problem: Send + Sync {
+    type Parsed: Send + Sync + 'static;
+
+    fn parse(&self, input: &str) -> Result<Self::Parsed, SolverError>;
+    fn solve_a(&self, parsed: &Self::Parsed, ctx: &Context) -> SolverResult;
+    fn solve_b(&self, parsed: &Self::Parsed, ctx: &Context) -> SolverResult;
+}
+
+pub type AnyParsed = dyn Any + Send + Sync;
+
+// Type-erased version of Problem, so that problems with different Parsed types can be boxed together
+pub trait DynProblem: Send + Sync {
+    fn parse(&self, input: &str) -> Result<Box<AnyParsed>, SolverError>;
+    fn solve_a(&self, parsed: &AnyParsed, ctx: &Context) -> SolverResult;
+    fn solve_b(&self, parsed: &AnyParsed, ctx: &Context) -> SolverResult;
+}
+
+fn downcast<P: Problem>(parsed: &AnyParsed) -> &P::Parsed {
+    parsed
+        .downcast_ref()
+        .expect("Parsed input should come from the same problem")
+}
+
+impl<P: Problem> DynProblem for P {
+    fn parse(&self, input: &str) -> Result<Box<AnyParsed>, SolverError> {
+        Ok(Box::new(Problem::parse(self, input)?))
+    }
+
+    fn solve_a(&self, parsed: &AnyParsed, ctx: &Context) -> SolverResult {
+        Problem::solve_a(self, downcast::<P>(parsed), ctx)
+    }
+
+    fn solve_b(&self, parsed: &AnyParsed, ctx: &Context) -> SolverResult {
+        Problem::solve_b(self, downcast::<P>(parsed), ctx)
+    }
 }
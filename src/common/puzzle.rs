@@ -0,0 +1,88 @@
+use std::fmt::Display;
+use std::str::FromStr;
+
+use crate::common::config;
+use crate::common::layout;
+
+// A single puzzle, identified by the year of the event and the day within it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Puzzle {
+    pub year: u32,
+    pub day: u32,
+}
+
+impl Puzzle {
+    pub fn new(year: u32, day: u32) -> Self {
+        Self { year, day }
+    }
+
+    // A day in the configured year
+    pub fn in_current_year(day: u32) -> Self {
+        Self::new(config::get().year, day)
+    }
+
+    // Directory holding one kind of data, e.g. inputs, for this puzzle's year: data/<year>/<kind>,
+    // or data/<kind> in the flat layout (see layout::data_dir)
+    pub fn data_dir(&self, kind: &str) -> String {
+        layout::data_dir(&config::get().data, self.year, kind)
+            .display()
+            .to_string()
+    }
+
+    // The puzzle's file in a data directory, e.g. data/2022/inputs/05.txt
+    pub fn data_path(&self, kind: &str, suffix: &str) -> String {
+        format!("{}/{:02}{suffix}", self.data_dir(kind), self.day)
+    }
+
+    // The puzzle's solution, src/days/dayNN.rs for the default year or src/days/<year>/dayNN.rs
+    pub fn source_path(&self) -> String {
+        let path = layout::source_path(self.year, self.day);
+        config::get().project_path(&path).display().to_string()
+    }
+}
+
+// Written as in run codes, with the year only when it is not the configured one, e.g. 5 or 2022:5
+impl Display for Puzzle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.year == config::get().year {
+            write!(f, "{}", self.day)
+        } else {
+            write!(f, "{}:{}", self.year, self.day)
+        }
+    }
+}
+
+impl FromStr for Puzzle {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // Only plain digits, as in run codes, so not +5
+        let number = |n: &str| -> Option<u32> {
+            n.chars()
+                .all(|c| c.is_ascii_digit())
+                .then(|| n.parse().ok())?
+        };
+        let (year, day) = match s.split_once(':') {
+            Some((year, day)) => (number(year), day),
+            None => (Some(config::get().year), s),
+        };
+        match (year, number(day)) {
+            (Some(year), Some(day)) if (1..=25).contains(&day) => Ok(Puzzle::new(year, day)),
+            _ => Err(format!("Invalid day {s}")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn days_are_plain_digits() {
+        assert_eq!("2022:5".parse(), Ok(Puzzle::new(2022, 5)));
+        assert_eq!("05".parse(), Ok(Puzzle::in_current_year(5)));
+        for invalid in ["+5", "2022:+5", "+2022:5", " 5", "", "0", "26"] {
+            assert!(invalid.parse::<Puzzle>().is_err(), "{invalid:?}");
+        }
+    }
+}
@@ -0,0 +1,59 @@
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::Path;
+
+use crate::common::puzzle::Puzzle;
+
+const TEMPLATE: &str = include_str!("../days/__template.rs");
+
+fn already_exists(path: &str) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::AlreadyExists,
+        format!("{path} already exists"),
+    )
+}
+
+// Creates the file, failing rather than overwriting if it is already there
+fn create_new(path: &str, contents: &str) -> io::Result<()> {
+    if let Some(dir) = Path::new(path).parent() {
+        fs::create_dir_all(dir)?;
+    }
+    OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(path)
+        .map_err(|e| match e.kind() {
+            io::ErrorKind::AlreadyExists => already_exists(path),
+            _ => e,
+        })?
+        .write_all(contents.as_bytes())
+}
+
+// Sets up a new day from __template.rs, along with its example input and answers file.
+// build.rs registers the new source file on the next build.
+// Returns the paths that were created; existing data files are left untouched.
+pub fn new_day(problem: Puzzle, from_example: Option<&str>) -> io::Result<Vec<String>> {
+    let source = problem.source_path();
+    let example = problem.data_path("examples", ".txt");
+    let answers = problem.data_path("answers", ".txt");
+
+    // Check everything up front, so that a refusal leaves nothing half created
+    if Path::new(&source).exists() {
+        return Err(already_exists(&source));
+    }
+    let example_contents = match from_example {
+        Some(_) if Path::new(&example).exists() => return Err(already_exists(&example)),
+        Some(path) => fs::read_to_string(path)?,
+        None => String::new(),
+    };
+
+    create_new(&source, TEMPLATE)?;
+    let mut created = vec![source];
+    for (path, contents) in [(example, example_contents.as_str()), (answers, "")] {
+        if !Path::new(&path).exists() {
+            create_new(&path, contents)?;
+            created.push(path);
+        }
+    }
+    Ok(created)
+}
@@ -0,0 +1,332 @@
+use aoc_parse::{parser, prelude::*};
+use colored::Colorize;
+use regex::Regex;
+use std::fmt::Display;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::Path;
+use std::str::FromStr;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::common::answer::Answer;
+use crate::common::fetch::{self, FetchError, USER_AGENT};
+use crate::common::http;
+use crate::common::io::Part;
+use crate::common::puzzle::Puzzle;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Hint {
+    TooHigh,
+    TooLow,
+}
+
+// What the site made of a submitted answer
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict {
+    Right,
+    Wrong(Option<Hint>),
+    RateLimited(Duration),
+    AlreadySolved,
+    // The page did not match any response we know, so its text is kept for the user to read
+    Unrecognised(String),
+}
+
+impl Verdict {
+    // A single token for the submission log
+    fn log_token(&self) -> String {
+        match self {
+            Verdict::Right => "right".to_string(),
+            Verdict::Wrong(None) => "wrong".to_string(),
+            Verdict::Wrong(Some(Hint::TooHigh)) => "too-high".to_string(),
+            Verdict::Wrong(Some(Hint::TooLow)) => "too-low".to_string(),
+            Verdict::RateLimited(wait) => format!("rate-limited:{}", wait.as_secs()),
+            Verdict::AlreadySolved => "already-solved".to_string(),
+            Verdict::Unrecognised(_) => "unrecognised".to_string(),
+        }
+    }
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let message = match self {
+            Verdict::Right => "right answer".bold().green(),
+            Verdict::Wrong(None) => "wrong answer".bold().red(),
+            Verdict::Wrong(Some(Hint::TooHigh)) => "wrong answer, too high".bold().red(),
+            Verdict::Wrong(Some(Hint::TooLow)) => "wrong answer, too low".bold().red(),
+            Verdict::RateLimited(wait) => {
+                format!("rate limited, wait {wait:?} before trying again")
+                    .bold()
+                    .yellow()
+            }
+            Verdict::AlreadySolved => "already solved".bold().cyan(),
+            Verdict::Unrecognised(text) => format!("unrecognised response: {text}").dimmed(),
+        };
+        message.fmt(f)
+    }
+}
+
+// The verdict is the text of the page's <article>, with its markup stripped
+fn article_text(html: &str) -> String {
+    let article = html
+        .split_once("<article")
+        .and_then(|(_, rest)| rest.split_once('>'))
+        .and_then(|(_, rest)| rest.split_once("</article>"))
+        .map_or(html, |(article, _)| article);
+    let tags = Regex::new(r"<[^>]*>").expect("Valid regex");
+    let text = tags.replace_all(article, "");
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn parse_wait(text: &str) -> Duration {
+    let wait = Regex::new(r"(?:(\d+)m )?(\d+)s left to wait").expect("Valid regex");
+    let secs = wait.captures(text).map_or(60, |caps| {
+        let minutes: u64 = caps.get(1).map_or(0, |m| m.as_str().parse().unwrap_or(0));
+        let seconds: u64 = caps[2].parse().unwrap_or(0);
+        minutes * 60 + seconds
+    });
+    Duration::from_secs(secs)
+}
+
+pub fn parse_verdict(html: &str) -> Verdict {
+    let text = article_text(html);
+    if text.contains("That's the right answer") {
+        Verdict::Right
+    } else if text.contains("That's not the right answer") {
+        let hint = if text.contains("too high") {
+            Some(Hint::TooHigh)
+        } else if text.contains("too low") {
+            Some(Hint::TooLow)
+        } else {
+            None
+        };
+        Verdict::Wrong(hint)
+    } else if text.contains("You gave an answer too recently") {
+        Verdict::RateLimited(parse_wait(&text))
+    } else if text.contains("Did you already complete it") {
+        Verdict::AlreadySolved
+    } else {
+        Verdict::Unrecognised(text)
+    }
+}
+
+fn log_path(problem: Puzzle) -> String {
+    problem.data_path("submissions", ".txt")
+}
+
+// A previous submission, as recorded in the log
+#[derive(Debug)]
+pub struct Submission {
+    pub part: Part,
+    pub verdict: Verdict,
+    pub answer: Answer,
+}
+
+// Every submission made for a problem, oldest first; a missing log means there are none
+pub fn load_log(problem: Puzzle) -> Result<Vec<Submission>, aoc_parse::ParseError> {
+    let contents = fs::read_to_string(log_path(problem)).unwrap_or_default();
+    let part_p = parser!({"a" => Part::A, "b" => Part::B});
+    let verdict_p = parser!({
+        "right" => Verdict::Right,
+        "wrong" => Verdict::Wrong(None),
+        "too-high" => Verdict::Wrong(Some(Hint::TooHigh)),
+        "too-low" => Verdict::Wrong(Some(Hint::TooLow)),
+        "rate-limited:" secs:u64 => Verdict::RateLimited(Duration::from_secs(secs)),
+        "already-solved" => Verdict::AlreadySolved,
+        "unrecognised" => Verdict::Unrecognised(String::new()),
+    });
+    let submissions = parser!(lines(u64 " " part_p " " verdict_p " " string(any_char+)))
+        .parse(&contents)?
+        .into_iter()
+        .map(|(_timestamp, part, verdict, answer)| {
+            let Ok(answer) = Answer::from_str(&answer);
+            Submission {
+                part,
+                verdict,
+                answer,
+            }
+        })
+        .collect();
+    Ok(submissions)
+}
+
+// Every submission is appended to ./data/<year>/submissions/NN.txt as a line like
+//   1701410000 b too-high 12345
+// giving the unix time, part, verdict and then the answer itself
+fn log_submission(path: &Path, part: Part, answer: &Answer, verdict: &Verdict) {
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+    let part = part.to_string().to_lowercase();
+    let line = format!("{timestamp} {part} {} {answer}\n", verdict.log_token());
+    let written = path
+        .parent()
+        .map_or(Ok(()), fs::create_dir_all)
+        .and_then(|_| {
+            OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(path)?
+                .write_all(line.as_bytes())
+        });
+    if let Err(e) = written {
+        eprintln!("Could not record submission: {e}");
+    }
+}
+
+pub fn submit(problem: Puzzle, part: Part, answer: &Answer) -> Result<Verdict, FetchError> {
+    send_answer(
+        problem,
+        part,
+        answer,
+        &fetch::base_url(),
+        fetch::session(),
+        &log_path(problem),
+    )
+}
+
+// As submit, with the server, session and submission log's path given explicitly
+fn send_answer(
+    problem: Puzzle,
+    part: Part,
+    answer: &Answer,
+    base_url: &str,
+    session: Option<String>,
+    log: &str,
+) -> Result<Verdict, FetchError> {
+    let session = session.ok_or(FetchError::NoSession)?;
+    let url = format!("{base_url}/{}/day/{}/answer", problem.year, problem.day);
+    let cookie = format!("session={session}");
+    let level = match part {
+        Part::A => "1",
+        Part::B => "2",
+    };
+    let response = http::post_form(
+        &url,
+        &[("Cookie", &cookie), ("User-Agent", USER_AGENT)],
+        &[("level", level), ("answer", &answer.to_string())],
+    )?;
+    fetch::check_response(problem, &response)?;
+    let verdict = parse_verdict(&response.body);
+    log_submission(Path::new(log), part, answer, &verdict);
+    Ok(verdict)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+    use std::process;
+
+    use super::*;
+    use crate::common::http::stub::serve_once;
+
+    // A verdict page as the site serves it, with the response in its <article>
+    fn page(article: &str) -> String {
+        format!(
+            "<!DOCTYPE html>\n<html lang=\"en-us\">\n<head>\n<title>Day 5 - Advent of Code 2023</title>\n</head>\n\
+             <body>\n<main>\n<article>{article}</article>\n</main>\n</body>\n</html>\n"
+        )
+    }
+
+    #[test]
+    fn right_answer() {
+        let html = page(
+            "<p>That's the right answer!  You are <span class=\"day-success\">one gold star</span> \
+             closer to restoring snow operations. <a href=\"/2023/day/5#part2\">[Continue to Part Two]</a></p>",
+        );
+        assert_eq!(parse_verdict(&html), Verdict::Right);
+    }
+
+    #[test]
+    fn wrong_answers() {
+        let wrong = |hint: &str| {
+            page(&format!(
+                "<p>That's not the right answer{hint}.  If you're stuck, make sure you're using the full \
+                 input data; there are also some general tips on the <a href=\"/2023/about\">about page</a>, \
+                 or you can ask for hints on the <a href=\"https://www.reddit.com/r/adventofcode/\" \
+                 target=\"_blank\">subreddit</a>.  Please wait one minute before trying again. \
+                 <a href=\"/2023/day/5\">[Return to Day 5]</a></p>"
+            ))
+        };
+        assert_eq!(
+            parse_verdict(&wrong("; your answer is too high")),
+            Verdict::Wrong(Some(Hint::TooHigh))
+        );
+        assert_eq!(
+            parse_verdict(&wrong("; your answer is too low")),
+            Verdict::Wrong(Some(Hint::TooLow))
+        );
+        assert_eq!(parse_verdict(&wrong("")), Verdict::Wrong(None));
+    }
+
+    #[test]
+    fn rate_limited() {
+        let limited = |left: &str| {
+            page(&format!(
+                "<p>You gave an answer too recently; you have to wait after submitting an answer before \
+                 trying again.  You have {left} left to wait. <a href=\"/2023/day/5\">[Return to Day 5]</a></p>"
+            ))
+        };
+        assert_eq!(
+            parse_verdict(&limited("1m 5s")),
+            Verdict::RateLimited(Duration::from_secs(65))
+        );
+        assert_eq!(
+            parse_verdict(&limited("42s")),
+            Verdict::RateLimited(Duration::from_secs(42))
+        );
+    }
+
+    #[test]
+    fn already_solved() {
+        let html = page(
+            "<p>You don't seem to be solving the right level.  Did you already complete it? \
+             <a href=\"/2023/day/5\">[Return to Day 5]</a></p>",
+        );
+        assert_eq!(parse_verdict(&html), Verdict::AlreadySolved);
+    }
+
+    #[test]
+    fn unrecognised_keeps_the_article_text() {
+        let html = page("<p>Something <em>unexpected</em>\n happened.</p>");
+        assert_eq!(
+            parse_verdict(&html),
+            Verdict::Unrecognised("Something unexpected happened.".to_string())
+        );
+    }
+
+    #[test]
+    fn submits_the_answer_and_logs_the_verdict() {
+        let html = page(
+            "<p>That's not the right answer; your answer is too low.  \
+             <a href=\"/2023/day/5\">[Return to Day 5]</a></p>",
+        );
+        let (base_url, server) = serve_once("200 OK", &html);
+        let dir = env::temp_dir().join(format!("aoc-submit-{}", process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        let log = dir.join("submissions").join("05.txt");
+
+        let answer = Answer::from(45);
+        let verdict = send_answer(
+            Puzzle::new(2023, 5),
+            Part::B,
+            &answer,
+            &base_url,
+            Some("abc123".to_string()),
+            log.to_str().unwrap(),
+        )
+        .unwrap();
+        assert_eq!(verdict, Verdict::Wrong(Some(Hint::TooLow)));
+
+        let request = server.join().unwrap();
+        assert!(request.starts_with("POST /2023/day/5/answer "), "{request}");
+        assert!(request.contains("session=abc123"), "{request}");
+        assert!(request.ends_with("level=2&answer=45"), "{request}");
+
+        let logged = fs::read_to_string(&log).unwrap();
+        let (timestamp, entry) = logged.split_once(' ').unwrap();
+        assert!(timestamp.parse::<u64>().is_ok(), "{logged}");
+        assert_eq!(entry, "b too-low 45\n");
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
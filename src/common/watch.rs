@@ -0,0 +1,181 @@
+use colored::Colorize;
+use std::collections::BTreeMap;
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
+use std::process::{self, Command};
+use std::time::{Duration, SystemTime};
+use std::{env, fs, thread};
+
+use crate::common::answers::AnswerStore;
+use crate::common::check;
+use crate::common::config;
+use crate::common::io::{Part, RunCode};
+use crate::common::puzzle::Puzzle;
+
+const POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+// Modification times of a set of files, compared between polls to spot changes
+type Snapshot = BTreeMap<PathBuf, Option<SystemTime>>;
+
+fn snapshot(paths: impl IntoIterator<Item = PathBuf>) -> Snapshot {
+    paths
+        .into_iter()
+        .map(|path| {
+            let modified = fs::metadata(&path).and_then(|m| m.modified()).ok();
+            (path, modified)
+        })
+        .collect()
+}
+
+fn files_in(dir: &Path) -> Vec<PathBuf> {
+    fs::read_dir(dir)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .map(|entry| entry.path())
+        .collect()
+}
+
+// Inputs and their sidecars, answers and submissions for the watched problems.
+// The directories themselves are included so that adding or removing an example is noticed.
+fn data_snapshot(problems: &[Puzzle]) -> Snapshot {
+    let mut paths = vec![];
+    for problem in problems {
+        for kind in ["examples", "inputs"] {
+            let dir = PathBuf::from(problem.data_dir(kind));
+            let prefix = format!("{:02}", problem.day);
+            paths.extend(files_in(&dir).into_iter().filter(|path| {
+                let name = path
+                    .file_name()
+                    .and_then(|n| n.to_str())
+                    .unwrap_or_default();
+                name.starts_with(&prefix)
+            }));
+            paths.push(dir);
+        }
+        paths.push(PathBuf::from(problem.data_path("answers", ".txt")));
+        paths.push(PathBuf::from(problem.data_path("submissions", ".txt")));
+    }
+    snapshot(paths)
+}
+
+// Everything that needs a rebuild when it changes
+fn source_snapshot() -> Snapshot {
+    let config = config::get();
+    let mut paths = vec![
+        config.project_path("Cargo.toml"),
+        config.project_path("build.rs"),
+    ];
+    let mut dirs = vec![config.project_path("src")];
+    while let Some(dir) = dirs.pop() {
+        paths.push(dir.clone());
+        for path in files_in(&dir) {
+            if path.is_dir() {
+                dirs.push(path);
+            } else {
+                paths.push(path);
+            }
+        }
+    }
+    snapshot(paths)
+}
+
+// Clears the terminal and shows each part's answer, its timing and how it compares with the store
+fn draw(code: &str, timeout: Option<Duration>) {
+    print!("\x1b[2J\x1b[H");
+    println!("{}", format!("Watching {code}, Ctrl-C to stop").dimmed());
+    let run_codes = RunCode::parse_list(code).expect("Valid run code");
+    for rc in run_codes {
+        let mut rc = rc.with_timeout(timeout);
+        println!();
+        rc.print_header();
+        if let Err(e) = rc.run() {
+            println!("{e}");
+            continue;
+        }
+        let store = match AnswerStore::load(rc.problem()) {
+            Ok(store) => store,
+            Err(e) => {
+                println!("Invalid answers file: {e}");
+                continue;
+            }
+        };
+        if let Some(time) = rc.parse_time() {
+            println!("Parse : {}", format!("{time:.2?}").dimmed());
+        }
+        for part in [Part::A, Part::B].into_iter().filter(|&part| rc.runs(part)) {
+            let verdict = check::verdict(&rc, part, &store);
+            let time = rc
+                .time(part)
+                .map_or(String::new(), |t| format!("({t:.2?})"));
+            println!(
+                "{part}     : {} {}",
+                check::verdict_line(rc.solution(part), &verdict),
+                time.dimmed()
+            );
+        }
+    }
+}
+
+// Replaces this process with the freshly built binary, which carries on watching
+fn restart(exe: &Path, args: &[OsString]) -> ! {
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::CommandExt;
+        let error = Command::new(exe).args(args).exec();
+        eprintln!("Could not restart {}: {error}", exe.display());
+        process::exit(1)
+    }
+    #[cfg(not(unix))]
+    {
+        let status = Command::new(exe).args(args).status();
+        process::exit(status.ok().and_then(|s| s.code()).unwrap_or(1))
+    }
+}
+
+fn rebuild() -> bool {
+    print!("\x1b[2J\x1b[H");
+    println!("{}", "Source changed, rebuilding".dimmed());
+    let mut build = Command::new("cargo");
+    build.arg("build").current_dir(&config::get().root);
+    if !cfg!(debug_assertions) {
+        build.arg("--release");
+    }
+    build.status().is_ok_and(|status| status.success())
+}
+
+// Re-runs the code whenever its data changes. Code changes rebuild the binary and restart into it;
+// if the build fails its errors stay on screen until the next change.
+pub fn watch(code: &str, timeout: Option<Duration>) -> ! {
+    // Taken up front, as rebuilding replaces the file the running binary was started from
+    let exe = env::current_exe().expect("Path to the running binary");
+    let args: Vec<_> = env::args_os().skip(1).collect();
+
+    let mut problems: Vec<_> = RunCode::parse_list(code)
+        .expect("Valid run code")
+        .iter()
+        .map(RunCode::problem)
+        .collect();
+    problems.dedup();
+
+    let mut sources = source_snapshot();
+    let mut data = data_snapshot(&problems);
+    draw(code, timeout);
+    loop {
+        thread::sleep(POLL_INTERVAL);
+        let new_sources = source_snapshot();
+        if new_sources != sources {
+            sources = new_sources;
+            if rebuild() {
+                restart(&exe, &args);
+            }
+            println!("{}", "Build failed, waiting for changes".bold().red());
+            continue;
+        }
+        let new_data = data_snapshot(&problems);
+        if new_data != data {
+            data = new_data;
+            draw(code, timeout);
+        }
+    }
+}
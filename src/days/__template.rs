@@ -1,15 +1,24 @@
-use crate::Problem;
+use crate::{Context, Problem, SolverError, SolverResult};
+// Imported up front as almost every day parses its input with aoc_parse
+#[allow(unused_imports)]
 use aoc_parse::{parser, prelude::*};
 pub struct Solution {}
 
 impl Problem for Solution {
+    type Parsed = ();
+
+    #[allow(unused_variables)]
+    fn parse(&self, input: &str) -> Result<Self::Parsed, SolverError> {
+        Err(SolverError::NotImplemented)
+    }
+
     #[allow(unused_variables)]
-    fn solve_a(&self, input: &str) -> Option<String> {
-        None
+    fn solve_a(&self, parsed: &Self::Parsed, ctx: &Context) -> SolverResult {
+        Err(SolverError::NotImplemented)
     }
 
     #[allow(unused_variables)]
-    fn solve_b(&self, input: &str) -> Option<String> {
-        None
+    fn solve_b(&self, parsed: &Self::Parsed, ctx: &Context) -> SolverResult {
+        Err(SolverError::NotImplemented)
     }
 }
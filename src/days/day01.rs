@@ -1,4 +1,4 @@
-use crate::Problem;
+use crate::{Context, Problem, SolverError, SolverResult};
 use regex::Regex;
 
 pub struct Solution {}
@@ -18,33 +18,42 @@ fn parse_digit_string(digit: &str) -> u32 {
     }
 }
 
+fn no_digit() -> SolverError {
+    SolverError::InvalidInput("line without a digit".to_string())
+}
+
 impl Problem for Solution {
-    fn solve_a(&self, input: &str) -> Option<String> {
-        let output_lines = input
-            .lines()
+    type Parsed = Vec<String>;
+
+    fn parse(&self, input: &str) -> Result<Self::Parsed, SolverError> {
+        Ok(input.lines().map(str::to_string).collect())
+    }
+
+    fn solve_a(&self, lines: &Self::Parsed, _ctx: &Context) -> SolverResult {
+        let output_lines = lines
+            .iter()
             .map(|line| line.chars().filter_map(|c| c.to_digit(10)))
             .map(|mut digits| {
-                let first = digits.next().unwrap();
-                let last = digits.last().unwrap_or(first);
-                first * 10 + last
+                let first = digits.next().ok_or_else(no_digit)?;
+                let last = digits.next_back().unwrap_or(first);
+                Ok(first * 10 + last)
             });
-        Some(output_lines.sum::<u32>().to_string())
+        Ok(output_lines.sum::<Result<u32, SolverError>>()?.into())
     }
 
-    fn solve_b(&self, input: &str) -> Option<String> {
-        let lines = input.lines();
+    fn solve_b(&self, lines: &Self::Parsed, _ctx: &Context) -> SolverResult {
         let re = Regex::new("^(one|two|three|four|five|six|seven|eight|nine|[0-9])").unwrap();
-        let line_values = lines.map(|line| {
-            let mut matches = (0..line.len()).filter_map(|i| {
+        let line_values = lines.iter().map(|line| {
+            let mut matches = line.char_indices().filter_map(|(i, _)| {
                 let substr = &line[i..];
                 re.find(substr)
             });
-            let first = matches.next().unwrap();
-            let last = matches.last().unwrap_or(first);
+            let first = matches.next().ok_or_else(no_digit)?;
+            let last = matches.next_back().unwrap_or(first);
             let tens = parse_digit_string(first.as_str());
             let units = parse_digit_string(last.as_str());
-            tens * 10 + units
+            Ok(tens * 10 + units)
         });
-        Some(line_values.sum::<u32>().to_string())
+        Ok(line_values.sum::<Result<u32, SolverError>>()?.into())
     }
 }
@@ -1,4 +1,4 @@
-use crate::Problem;
+use crate::{Context, Problem, SolverError, SolverResult};
 use aoc_parse::{parser, prelude::*};
 
 #[derive(Debug)]
@@ -17,13 +17,18 @@ impl Hand {
         }
     }
 
-    fn add(&mut self, count: u32, color: &str) {
+    fn add(&mut self, count: u32, color: &str) -> Result<(), SolverError> {
         match color {
             "red" => self.red += count,
             "green" => self.green += count,
             "blue" => self.blue += count,
-            _ => panic!("Unrecognised color"),
+            _ => {
+                return Err(SolverError::InvalidInput(format!(
+                    "unrecognised color {color}"
+                )))
+            }
         }
+        Ok(())
     }
 
     fn all_leq_than(&self, other: &Self) -> bool {
@@ -36,7 +41,7 @@ impl Hand {
 }
 
 #[derive(Debug)]
-struct Game {
+pub struct Game {
     index: u32,
     hands: Vec<Hand>,
 }
@@ -50,7 +55,7 @@ impl Game {
     }
 }
 
-fn build_game(input: (u32, Vec<Vec<(u32, String)>>)) -> Game {
+fn build_game(input: (u32, Vec<Vec<(u32, String)>>)) -> Result<Game, SolverError> {
     let index = input.0;
     let hands_raw = input.1;
     let hands = hands_raw
@@ -58,15 +63,15 @@ fn build_game(input: (u32, Vec<Vec<(u32, String)>>)) -> Game {
         .map(|hand| {
             let mut new_hand = Hand::empty();
             for (count, color) in hand {
-                new_hand.add(count, color.as_str())
+                new_hand.add(count, color.as_str())?;
             }
-            new_hand
+            Ok(new_hand)
         })
-        .collect();
-    Game { index, hands }
+        .collect::<Result<_, SolverError>>()?;
+    Ok(Game { index, hands })
 }
 
-fn parse_games(input: &str) -> Vec<Game> {
+fn parse_games(input: &str) -> Result<Vec<Game>, SolverError> {
     let hand_p = parser!(
         repeat_sep(u32 " " string(alpha+) , ", ")
     );
@@ -75,35 +80,32 @@ fn parse_games(input: &str) -> Vec<Game> {
             "Game " u32 ": " repeat_sep(hand_p, "; ")
         )
     );
-    p.parse(input)
-        .unwrap()
-        .into_iter()
-        .map(build_game)
-        .collect()
+    p.parse(input)?.into_iter().map(build_game).collect()
 }
 
 pub struct Solution {}
 
 impl Problem for Solution {
-    fn solve_a(&self, input: &str) -> Option<String> {
-        let games = parse_games(input);
+    type Parsed = Vec<Game>;
+
+    fn parse(&self, input: &str) -> Result<Self::Parsed, SolverError> {
+        parse_games(input)
+    }
+
+    fn solve_a(&self, games: &Self::Parsed, ctx: &Context) -> SolverResult {
         let ref_hand = Hand {
-            red: 12,
-            green: 13,
-            blue: 14,
+            red: ctx.get_or("red", 12)?,
+            green: ctx.get_or("green", 13)?,
+            blue: ctx.get_or("blue", 14)?,
         };
         let possible_games = games
-            .into_iter()
+            .iter()
             .filter(|game| game.hands.iter().all(|h| h.all_leq_than(&ref_hand)));
-        Some(possible_games.map(|g| g.index).sum::<u32>().to_string())
+        Ok(possible_games.map(|g| g.index).sum::<u32>().into())
     }
 
-    fn solve_b(&self, input: &str) -> Option<String> {
-        let games = parse_games(input);
-        let total_power = games
-            .into_iter()
-            .map(|g| g.min_ref_hand().power())
-            .sum::<u32>();
-        Some(total_power.to_string())
+    fn solve_b(&self, games: &Self::Parsed, _ctx: &Context) -> SolverResult {
+        let total_power = games.iter().map(|g| g.min_ref_hand().power()).sum::<u32>();
+        Ok(total_power.into())
     }
 }
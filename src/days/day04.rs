@@ -1,12 +1,12 @@
 use std::collections::{HashMap, HashSet};
 
-use crate::Problem;
+use crate::{Context, Problem, SolverError, SolverResult};
 use aoc_parse::{parser, prelude::*};
 
 pub struct Solution {}
 
 #[derive(Debug, Clone)]
-struct Card {
+pub struct Card {
     index: usize,
     winning: Vec<usize>,
     played: Vec<usize>,
@@ -36,7 +36,7 @@ impl Card {
     }
 }
 
-fn parse_input(input: &str) -> Vec<Card> {
+fn parse_input(input: &str) -> Result<Vec<Card>, aoc_parse::ParseError> {
     let p = parser!(
         lines(
             "Card" " "+ usize ":" " "+
@@ -45,8 +45,8 @@ fn parse_input(input: &str) -> Vec<Card> {
             repeat_sep(usize, " "+)
         )
     );
-    let parsed_lines = p.parse(input).unwrap();
-    parsed_lines
+    let parsed_lines = p.parse(input)?;
+    let cards = parsed_lines
         .into_iter()
         .map(|line| {
             let index = line.1 - 1;
@@ -58,18 +58,23 @@ fn parse_input(input: &str) -> Vec<Card> {
                 played,
             }
         })
-        .collect()
+        .collect();
+    Ok(cards)
 }
 
 impl Problem for Solution {
-    fn solve_a(&self, input: &str) -> Option<String> {
-        let cards = parse_input(input);
-        let total_score: u32 = cards.into_iter().map(|c| c.score()).sum();
-        Some(total_score.to_string())
+    type Parsed = Vec<Card>;
+
+    fn parse(&self, input: &str) -> Result<Self::Parsed, SolverError> {
+        Ok(parse_input(input)?)
+    }
+
+    fn solve_a(&self, cards: &Self::Parsed, _ctx: &Context) -> SolverResult {
+        let total_score: u32 = cards.iter().map(|c| c.score()).sum();
+        Ok(total_score.into())
     }
 
-    fn solve_b(&self, input: &str) -> Option<String> {
-        let cards = parse_input(input);
+    fn solve_b(&self, cards: &Self::Parsed, _ctx: &Context) -> SolverResult {
         let mut winnings_by_card: HashMap<usize, u32> = HashMap::new();
         let n_cards = cards.len();
         let mut total_winnings = n_cards as u32;
@@ -82,6 +87,6 @@ impl Problem for Solution {
             winnings_by_card.insert(i, i_winnings);
             total_winnings += i_winnings;
         }
-        Some(total_winnings.to_string())
+        Ok(total_winnings.into())
     }
 }
@@ -1,36 +1,33 @@
 use rayon::prelude::*;
-use std::ops::Range;
 
-use crate::Problem;
+use crate::common::interval::{Interval, IntervalSet};
+use crate::{Context, Problem, SolverError, SolverResult};
 use aoc_parse::{parser, prelude::*};
 
 pub struct Solution {}
 
 #[derive(Debug)]
 struct SubMap {
-    source_start: isize,
-    dest_start: isize,
-    len: isize,
+    source_start: i64,
+    dest_start: i64,
+    len: i64,
 }
 
 impl SubMap {
     fn domain(&self) -> Interval {
-        Interval::build(self.source_start, self.len)
+        Interval::with_len(self.source_start, self.len)
     }
 
     fn image(&self) -> Interval {
-        Interval::build(self.dest_start, self.len)
+        Interval::with_len(self.dest_start, self.len)
     }
 
-    fn map(&self, input: isize) -> isize {
-        input - (self.source_start - self.dest_start)
+    // How far the sub map moves everything in its domain
+    fn offset(&self) -> i64 {
+        self.dest_start - self.source_start
     }
 
-    fn inverse_map(&self, input: isize) -> isize {
-        input - (self.dest_start - self.source_start)
-    }
-
-    fn parse(raw: (isize, isize, isize)) -> Self {
+    fn parse(raw: (i64, i64, i64)) -> Self {
         SubMap {
             dest_start: raw.0,
             source_start: raw.1,
@@ -39,161 +36,238 @@ impl SubMap {
     }
 }
 
+type RawResourceMap = ((String, String), Vec<(i64, i64, i64)>);
+
 #[derive(Debug)]
 struct ResourceMap {
     sub_maps: Vec<SubMap>,
 }
 impl ResourceMap {
-    fn map(&self, input: isize) -> isize {
+    fn map(&self, input: i64) -> i64 {
         for sub_map in self.sub_maps.iter() {
             if sub_map.domain().contains(input) {
-                return sub_map.map(input);
+                return input + sub_map.offset();
             }
         }
-        return input;
-    }
-
-    fn inverse_map(&self, input: isize) -> isize {
-        for sub_map in self.sub_maps.iter() {
-            if sub_map.image().contains(input) {
-                return sub_map.inverse_map(input);
-            }
-        }
-        return input;
+        input
     }
 
-    fn parse(raw_map: ((String, String), Vec<(isize, isize, isize)>)) -> Self {
+    fn parse(raw_map: RawResourceMap) -> Self {
         let mut sub_maps: Vec<_> = raw_map.1.into_iter().map(SubMap::parse).collect();
         sub_maps.sort_by_key(|m| m.source_start);
         Self { sub_maps }
     }
 
-    fn change_points<'a>(&'a self) -> impl Iterator<Item = isize> + 'a {
-        self.sub_maps.iter().flat_map(|sub_map| {
-            vec![sub_map.source_start, sub_map.source_start + sub_map.len].into_iter()
-        })
+    fn change_points(&self) -> Vec<i64> {
+        self.sub_maps
+            .iter()
+            .flat_map(|sub_map| [sub_map.domain().start, sub_map.domain().end])
+            .collect()
+    }
+
+    // Everything the inputs are mapped to. Split at the sub map boundaries, each piece is
+    // moved by a single offset, so can be mapped whole.
+    fn image(&self, inputs: &IntervalSet) -> IntervalSet {
+        inputs
+            .split_at(&self.change_points())
+            .into_iter()
+            .map(|piece| piece.shift(self.map(piece.start) - piece.start))
+            .collect()
+    }
+
+    // Everything that is mapped into the outputs
+    fn preimage(&self, outputs: &IntervalSet) -> IntervalSet {
+        let domains: IntervalSet = self.sub_maps.iter().map(SubMap::domain).collect();
+        // Inputs outside every sub map are mapped to themselves
+        let unmapped = outputs.difference(&domains);
+        self.sub_maps
+            .iter()
+            .map(|sub_map| {
+                let image = IntervalSet::from(sub_map.image());
+                outputs.intersection(&image).shift(-sub_map.offset())
+            })
+            .fold(unmapped, |preimage, pulled_back| preimage.union(&pulled_back))
     }
 }
 
 #[derive(Debug)]
-struct Almanac(Vec<ResourceMap>);
+pub struct Almanac(Vec<ResourceMap>);
 
 impl Almanac {
-    fn map(&self, mut input: isize) -> isize {
+    fn map(&self, mut input: i64) -> i64 {
         for r_map in self.0.iter() {
             input = r_map.map(input);
         }
         input
     }
 
-    fn all_change_points(&self) -> Vec<isize> {
-        let mut changes: Vec<isize> = vec![];
-        for r_map in self.0.iter().rev() {
-            // Pull back previous change points along this map
-            for cp in changes.iter_mut() {
-                *cp = r_map.inverse_map(*cp);
-            }
-            // Add change points for this function
-            let new_changes = r_map.change_points();
-            changes.extend(new_changes);
-        }
-        changes
+    // Exactly the locations that some seed is mapped to
+    fn image(&self, seeds: &IntervalSet) -> IntervalSet {
+        self.0
+            .iter()
+            .fold(seeds.clone(), |inputs, r_map| r_map.image(&inputs))
     }
-}
 
-struct Interval {
-    start: isize,
-    length: isize,
-}
-
-impl Interval {
-    fn build(start: isize, length: isize) -> Self {
-        Interval { start, length }
+    // Exactly the seeds that are mapped to one of the locations
+    fn preimage(&self, locations: &IntervalSet) -> IntervalSet {
+        self.0
+            .iter()
+            .rev()
+            .fold(locations.clone(), |outputs, r_map| r_map.preimage(&outputs))
     }
 
-    fn end_exclusive(&self) -> isize {
-        self.start + self.length
-    }
-
-    fn interval_iter(&self) -> Range<isize> {
-        println!("Starting interval");
-        self.start..self.end_exclusive()
-    }
-
-    fn contains(&self, elem: isize) -> bool {
-        (self.start <= elem) & (elem < self.start + self.length)
+    // Which of the seeds end up at the location. Neither part needs this, only the tests.
+    #[cfg_attr(not(test), allow(dead_code))]
+    fn seeds_reaching(&self, seeds: &IntervalSet, location: i64) -> IntervalSet {
+        let location = IntervalSet::from(Interval::with_len(location, 1));
+        self.preimage(&location).intersection(seeds)
     }
 }
 
-fn parse_input(input: &str) -> (Vec<isize>, Almanac) {
-    let seed_p = parser!(line("seeds: " repeat_sep(isize, " ")));
+fn parse_input(input: &str) -> Result<(Vec<i64>, Almanac), aoc_parse::ParseError> {
+    let seed_p = parser!(line("seeds: " repeat_sep(i64, " ")));
     let map_p = parser!(
         line(string(alpha+) "-to-" string(alpha+) " map:")
         lines(
-            isize " " isize " " isize
+            i64 " " i64 " " i64
         )
     );
     let input_p = parser!(
         section(seed_p)
         sections(map_p)
     );
-    let parsed_raw = input_p.parse(input).unwrap();
+    let parsed_raw = input_p.parse(input)?;
     let seeds = parsed_raw.0;
     let almanac = Almanac(parsed_raw.1.into_iter().map(ResourceMap::parse).collect());
-    (seeds, almanac)
+    Ok((seeds, almanac))
 }
 
-#[allow(dead_code)]
-fn solve_b_brute_force(mut intervals: Vec<Interval>, almanac: Almanac) -> isize {
-    intervals.sort_by_key(|it| it.start);
-    for i in 0..(intervals.len() - 1) {
-        assert!(intervals[i].end_exclusive() <= intervals[i + 1].start)
+// Part b reads the seeds as pairs of a start and a length
+fn seed_ranges(seeds_and_lengths: &[i64]) -> Result<IntervalSet, SolverError> {
+    let pairs = seeds_and_lengths.chunks_exact(2);
+    if !pairs.remainder().is_empty() {
+        return Err(SolverError::InvalidInput(
+            "seeds should come in pairs of a start and a length".to_string(),
+        ));
     }
+    Ok(pairs
+        .map(|pair| Interval::with_len(pair[0], pair[1]))
+        .collect())
+}
 
-    let possible_seeds = intervals.iter().flat_map(|it| it.interval_iter());
+impl Problem for Solution {
+    type Parsed = (Vec<i64>, Almanac);
 
-    possible_seeds
-        .par_bridge()
-        .map(|s| almanac.map(s))
-        .min()
-        .unwrap()
-}
+    fn parse(&self, input: &str) -> Result<Self::Parsed, SolverError> {
+        Ok(parse_input(input)?)
+    }
+
+    fn solve_a(&self, (seeds, almanac): &Self::Parsed, _ctx: &Context) -> SolverResult {
+        let locations = seeds.par_iter().map(|&s| almanac.map(s));
+        let min_loc = locations
+            .min()
+            .ok_or(SolverError::InvalidInput("no seeds".to_string()))?;
+        Ok(min_loc.into())
+    }
 
-#[allow(dead_code)]
-fn solve_b_intelligently(intervals: Vec<Interval>, almanac: Almanac) -> isize {
-    let change_points = almanac.all_change_points();
-    let mut change_points: Vec<_> = change_points
-        .into_iter()
-        .filter(|cp| intervals.iter().any(|itvl| itvl.contains(*cp)))
-        .collect();
-    change_points.extend(intervals.iter().map(|itvl| itvl.start));
-    change_points.push(0);
-
-    change_points
-        .into_iter()
-        .map(|s| almanac.map(s))
-        .min()
-        .unwrap()
+    fn solve_b(&self, (seeds_and_lengths, almanac): &Self::Parsed, _ctx: &Context) -> SolverResult {
+        let seeds = seed_ranges(seeds_and_lengths)?;
+        let min_location = almanac
+            .image(&seeds)
+            .min()
+            .ok_or(SolverError::InvalidInput("no seeds".to_string()))?;
+        Ok(min_location.into())
+    }
 }
 
-impl Problem for Solution {
-    fn solve_a(&self, input: &str) -> Option<String> {
-        let (seeds, almanac) = parse_input(input);
-        let locations = seeds.into_iter().par_bridge().map(|s| almanac.map(s));
-        let min_loc = locations.min().unwrap();
-        Some(min_loc.to_string())
-    }
-
-    fn solve_b(&self, input: &str) -> Option<String> {
-        let (seeds_and_lengths, almanac) = parse_input(input);
-        let intervals: Vec<_> = seeds_and_lengths
-            .chunks(2)
-            .map(|pair| Interval {
-                start: pair[0],
-                length: pair[1],
-            })
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use super::*;
+
+    const EXAMPLE: &str = "\
+seeds: 79 14 55 13
+
+seed-to-soil map:
+50 98 2
+52 50 48
+
+soil-to-fertilizer map:
+0 15 37
+37 52 2
+39 0 15
+
+fertilizer-to-water map:
+49 53 8
+0 11 42
+42 0 7
+57 7 4
+
+water-to-light map:
+88 18 7
+18 25 70
+
+light-to-temperature map:
+45 77 23
+81 45 19
+68 64 13
+
+temperature-to-humidity map:
+0 69 1
+1 0 69
+
+humidity-to-location map:
+60 56 37
+56 93 4
+";
+
+    fn example() -> (IntervalSet, Almanac) {
+        let (seeds_and_lengths, almanac) = parse_input(EXAMPLE).unwrap();
+        (seed_ranges(&seeds_and_lengths).unwrap(), almanac)
+    }
+
+    fn members(set: &IntervalSet) -> BTreeSet<i64> {
+        set.iter().flat_map(Interval::iter).collect()
+    }
+
+    #[test]
+    fn locations_match_mapping_every_seed() {
+        let (seeds, almanac) = example();
+        let locations = almanac.image(&seeds);
+        let expected: BTreeSet<i64> = members(&seeds)
+            .into_iter()
+            .map(|seed| almanac.map(seed))
             .collect();
-        let min_location = solve_b_intelligently(intervals, almanac);
-        Some(min_location.to_string())
+        assert_eq!(members(&locations), expected);
+        assert_eq!(locations.min(), Some(46));
+        assert_eq!(locations.max(), expected.last().copied());
+        assert_eq!(locations.total_len(), expected.len() as u64);
+    }
+
+    #[test]
+    fn seeds_reaching_a_location() {
+        let (seeds, almanac) = example();
+        assert_eq!(members(&almanac.seeds_reaching(&seeds, 46)), BTreeSet::from([82]));
+        assert!(almanac.seeds_reaching(&seeds, 45).is_empty());
+        for location in 0..110 {
+            let expected: BTreeSet<i64> = members(&seeds)
+                .into_iter()
+                .filter(|&seed| almanac.map(seed) == location)
+                .collect();
+            assert_eq!(
+                members(&almanac.seeds_reaching(&seeds, location)),
+                expected,
+                "location {location}"
+            );
+        }
+    }
+
+    #[test]
+    fn unpaired_seed_is_an_error() {
+        assert!(matches!(
+            seed_ranges(&[79, 14, 55]),
+            Err(SolverError::InvalidInput(_))
+        ));
     }
 }
@@ -1,11 +1,11 @@
 use std::{iter::zip, ops::RangeInclusive};
 
-use crate::Problem;
+use crate::{Context, Problem, SolverError, SolverResult};
 use aoc_parse::{parser, prelude::*};
 pub struct Solution {}
 
 #[derive(Debug)]
-struct Record {
+pub struct Record {
     time: u64,
     distance: u64,
 }
@@ -49,46 +49,53 @@ impl Record {
     }
 }
 
-fn parse_input_a(input: &str) -> Vec<Record> {
+fn parse_input_a(input: &str) -> Result<Vec<Record>, aoc_parse::ParseError> {
     let p = parser!(
         line("Time:" " "+ repeat_sep(u64," "+))
         line("Distance:" " "+ repeat_sep(u64," "+))
     );
-    let parsed = p.parse(input).unwrap();
+    let parsed = p.parse(input)?;
     let times = parsed.0 .1;
     let distances = parsed.1 .1;
-    zip(times.into_iter(), distances.into_iter())
+    let records = zip(times, distances)
         .map(|(time, distance)| Record { time, distance })
-        .collect()
+        .collect();
+    Ok(records)
 }
 
-fn ignore_kerning(input: String) -> u64 {
+fn ignore_kerning(input: String) -> Result<u64, SolverError> {
     let mut time = input;
     time.retain(|c| !c.is_whitespace());
-    time.parse().unwrap()
+    time.parse()
+        .map_err(|_| SolverError::InvalidInput(format!("{time} is not a number")))
 }
 
-fn parse_input_b(input: &str) -> Record {
+fn parse_input_b(input: &str) -> Result<Record, SolverError> {
     let p = parser!(
         line("Time:" string(any_char+))
         line("Distance:" string(any_char+))
             );
-    let parsed = p.parse(input).unwrap();
-    Record {
-        time: ignore_kerning(parsed.0),
-        distance: ignore_kerning(parsed.1),
-    }
+    let parsed = p.parse(input)?;
+    Ok(Record {
+        time: ignore_kerning(parsed.0)?,
+        distance: ignore_kerning(parsed.1)?,
+    })
 }
 
 impl Problem for Solution {
-    fn solve_a(&self, input: &str) -> Option<String> {
-        let records = parse_input_a(input);
-        let answer: u64 = records.into_iter().map(|r| r.n_ways_to_beat_v2()).product();
-        Some(answer.to_string())
+    // Part A reads several races, part B reads the same lines as one race
+    type Parsed = (Vec<Record>, Record);
+
+    fn parse(&self, input: &str) -> Result<Self::Parsed, SolverError> {
+        Ok((parse_input_a(input)?, parse_input_b(input)?))
+    }
+
+    fn solve_a(&self, (records, _): &Self::Parsed, _ctx: &Context) -> SolverResult {
+        let answer: u64 = records.iter().map(|r| r.n_ways_to_beat_v2()).product();
+        Ok(answer.into())
     }
 
-    fn solve_b(&self, input: &str) -> Option<String> {
-        let record = parse_input_b(input);
-        Some(record.n_ways_to_beat_v2().to_string())
+    fn solve_b(&self, (_, record): &Self::Parsed, _ctx: &Context) -> SolverResult {
+        Ok(record.n_ways_to_beat_v2().into())
     }
 }
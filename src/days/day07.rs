@@ -1,6 +1,6 @@
 use std::{cmp::Reverse, collections::HashMap};
 
-use crate::Problem;
+use crate::{Context, Problem, SolverError, SolverResult};
 use aoc_parse::{parser, prelude::*};
 pub struct Solution {}
 
@@ -51,14 +51,10 @@ struct Hand {
     string: String,
 }
 
-fn build_counts(str: &String) -> HashMap<char, usize> {
+fn build_counts(str: &str) -> HashMap<char, usize> {
     let mut counts: HashMap<char, usize> = HashMap::new();
     for c in str.chars() {
-        if !counts.contains_key(&c) {
-            counts.insert(c, 0);
-        }
-        let current_count = counts.get(&c).unwrap();
-        counts.insert(c, current_count + 1);
+        *counts.entry(c).or_insert(0) += 1;
     }
     counts
 }
@@ -111,30 +107,38 @@ impl Hand {
 }
 
 #[derive(Debug)]
-struct Row {
+pub struct Row {
     hand: Hand,
     bid: u32,
 }
 
+const CARDS: &str = "23456789TJQKA";
+
 impl Row {
-    fn parse(input: (String, u32)) -> Row {
-        Row {
-            hand: Hand { string: input.0 },
-            bid: input.1,
+    fn parse(input: (String, u32)) -> Result<Row, SolverError> {
+        let (string, bid) = input;
+        if string.chars().count() != 5 || !string.chars().all(|c| CARDS.contains(c)) {
+            return Err(SolverError::InvalidInput(format!(
+                "{string} is not a hand of five cards from {CARDS}"
+            )));
         }
+        Ok(Row {
+            hand: Hand { string },
+            bid,
+        })
     }
 }
 
-fn parse_input(input: &str) -> Vec<Row> {
+fn parse_input(input: &str) -> Result<Vec<Row>, SolverError> {
     let p = parser!(lines(
         string(any_char+) " " u32
     ));
-    let parsed_raw = p.parse(input).unwrap();
+    let parsed_raw = p.parse(input)?;
     parsed_raw.into_iter().map(Row::parse).collect()
 }
 
-fn solve(input: &str, j_is_joker: bool) -> u32 {
-    let mut rows = parse_input(input);
+fn solve(rows: &[Row], j_is_joker: bool) -> u32 {
+    let mut rows: Vec<_> = rows.iter().collect();
     rows.sort_by_key(|row| row.hand.hand_strength(j_is_joker));
     rows.into_iter()
         .enumerate()
@@ -143,11 +147,17 @@ fn solve(input: &str, j_is_joker: bool) -> u32 {
 }
 
 impl Problem for Solution {
-    fn solve_a(&self, input: &str) -> Option<String> {
-        Some(solve(input, false).to_string())
+    type Parsed = Vec<Row>;
+
+    fn parse(&self, input: &str) -> Result<Self::Parsed, SolverError> {
+        parse_input(input)
+    }
+
+    fn solve_a(&self, rows: &Self::Parsed, _ctx: &Context) -> SolverResult {
+        Ok(solve(rows, false).into())
     }
 
-    fn solve_b(&self, input: &str) -> Option<String> {
-        Some(solve(input, true).to_string())
+    fn solve_b(&self, rows: &Self::Parsed, _ctx: &Context) -> SolverResult {
+        Ok(solve(rows, true).into())
     }
 }
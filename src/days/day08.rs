@@ -1,7 +1,7 @@
 use hashbrown::HashMap;
 use num::integer::lcm;
 
-use crate::Problem;
+use crate::{Context, Problem, SolverError, SolverResult};
 use aoc_parse::{parser, prelude::*};
 pub struct Solution {}
 
@@ -12,35 +12,29 @@ fn string_to_key(s: String) -> Key {
     (chars_vec[0], chars_vec[1], chars_vec[2])
 }
 
-struct Tree(HashMap<Key, (Key, Key)>);
+pub struct Tree(HashMap<Key, (Key, Key)>);
 
 impl Tree {
-    fn follow(&self, start: &mut Key, next_move: &Move) {
-        let pair = self.0.get(start).expect("start should be a key");
+    fn follow(&self, start: &mut Key, next_move: &Move) -> Result<(), SolverError> {
+        let pair = self.0.get(start).ok_or_else(|| {
+            let (a, b, c) = start;
+            SolverError::InvalidInput(format!("no node {a}{b}{c}"))
+        })?;
         *start = match next_move {
             Move::Left => pair.0,
             Move::Right => pair.1,
         };
+        Ok(())
     }
 }
 
-enum Move {
+pub enum Move {
     Left,
     Right,
 }
 
-impl Move {
-    fn parse(c: char) -> Move {
-        match c {
-            'L' => Move::Left,
-            'R' => Move::Right,
-            _ => panic!("Unrecognised move"),
-        }
-    }
-}
-
-fn parse_input(input: &str) -> (Vec<Move>, Tree) {
-    let moves_p = parser!(string(char_of("LR")+));
+fn parse_input(input: &str) -> Result<(Vec<Move>, Tree), aoc_parse::ParseError> {
+    let moves_p = parser!({"L" => Move::Left, "R" => Move::Right}+);
     let node_p = parser!(
         string(upper+) " = (" string(upper+) ", " string(upper+) ")"
     );
@@ -48,8 +42,8 @@ fn parse_input(input: &str) -> (Vec<Move>, Tree) {
         section(line(moves_p))
         section(lines(node_p))
     );
-    let parsed_raw = p.parse(input).unwrap();
-    let moves: Vec<_> = parsed_raw.0.chars().map(Move::parse).collect();
+    let parsed_raw = p.parse(input)?;
+    let moves = parsed_raw.0;
 
     let mut tree = HashMap::new();
     for (root, left, right) in parsed_raw.1 {
@@ -58,28 +52,33 @@ fn parse_input(input: &str) -> (Vec<Move>, Tree) {
             (string_to_key(left), string_to_key(right)),
         );
     }
-    (moves, Tree(tree))
+    Ok((moves, Tree(tree)))
 }
 
 impl Problem for Solution {
-    fn solve_a(&self, input: &str) -> Option<String> {
-        let (moves, tree) = parse_input(input);
+    type Parsed = (Vec<Move>, Tree);
+
+    fn parse(&self, input: &str) -> Result<Self::Parsed, SolverError> {
+        Ok(parse_input(input)?)
+    }
+
+    fn solve_a(&self, (moves, tree): &Self::Parsed, ctx: &Context) -> SolverResult {
         let mut move_cycle = moves.iter().cycle();
         let mut key = ('A', 'A', 'A');
         let mut n_moves = 0;
 
+        // A map that never leads to ZZZ would be walked forever
         while key != ('Z', 'Z', 'Z') {
+            ctx.check_cancelled()?;
             let next_move = move_cycle.next().unwrap();
-            tree.follow(&mut key, next_move);
+            tree.follow(&mut key, next_move)?;
             n_moves += 1;
         }
 
-        Some(n_moves.to_string())
+        Ok(n_moves.into())
     }
 
-    fn solve_b(&self, input: &str) -> Option<String> {
-        let (moves, tree) = parse_input(input);
-
+    fn solve_b(&self, (moves, tree): &Self::Parsed, ctx: &Context) -> SolverResult {
         let ends_a_or_z = |k: &Key| k.2 == 'A' || k.2 == 'Z';
         let ends_a = |k: &Key| k.2 == 'A';
 
@@ -91,19 +90,20 @@ impl Problem for Solution {
         // When does a (*, *, A|Z) key loop to another such key?
         // How long does it take
         for start_key in keys.iter() {
-            let mut key = start_key.clone();
+            let mut key = *start_key;
             let mut cont = true;
             let mut move_cycle = moves.iter().cycle();
             let mut n_moves = 0;
             while cont {
+                ctx.check_cancelled()?;
                 let next_move = move_cycle.next().unwrap();
-                tree.follow(&mut key, next_move);
+                tree.follow(&mut key, next_move)?;
                 n_moves += 1;
 
                 cont = !ends_a_or_z(&key);
             }
-            endpoints.insert(start_key.clone(), key);
-            lengths.insert(start_key.clone(), n_moves);
+            endpoints.insert(*start_key, key);
+            lengths.insert(*start_key, n_moves);
         }
 
         let mut answer = 1;
@@ -112,17 +112,19 @@ impl Problem for Solution {
         // and the loop goes A_key -> Z_key -> Z_key -> ...
         // with both paths being the same length
         for start_key in keys.into_iter().filter(ends_a) {
-            let endp = endpoints.get(&start_key).unwrap();
-            let second_endp = endpoints.get(endp).unwrap();
-            assert_eq!(endp, second_endp);
-            let l1 = lengths.get(&start_key).unwrap();
-            let l2 = lengths.get(endp).unwrap();
-            assert_eq!(l1, l2);
-            answer = lcm(answer, *l1);
+            let endp = endpoints[&start_key];
+            let l1 = lengths[&start_key];
+            if endpoints.get(&endp) != Some(&endp) || lengths.get(&endp) != Some(&l1) {
+                return Err(SolverError::InvalidInput(
+                    "paths do not loop back to their Z node in the same number of steps"
+                        .to_string(),
+                ));
+            }
+            answer = lcm(answer, l1);
         }
 
         // Hence answer is LCM of cycle length
 
-        Some(answer.to_string())
+        Ok(answer.into())
     }
 }
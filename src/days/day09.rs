@@ -1,4 +1,4 @@
-use crate::Problem;
+use crate::{Context, Problem, SolverError, SolverResult};
 use aoc_parse::{parser, prelude::*};
 pub struct Solution {}
 
@@ -6,9 +6,9 @@ fn get_differences<'a>(input: &'a [i64]) -> impl Iterator<Item = i64> + 'a {
     input.windows(2).map(|w| w[1] - w[0])
 }
 
-fn parse_input(input: &str) -> Vec<Vec<i64>> {
+fn parse_input(input: &str) -> Result<Vec<Vec<i64>>, aoc_parse::ParseError> {
     let p = parser!(lines(repeat_sep(i64, " ")));
-    p.parse(input).unwrap()
+    p.parse(input)
 }
 
 fn all_zero(input: &[i64]) -> bool {
@@ -27,15 +27,13 @@ fn compute_extrapolated_end(input: Vec<i64>) -> i64 {
         // Clear the other vector
         next_vec.clear();
         // Fill the other vector with differences
-        next_vec.extend(get_differences(&working_vec));
+        next_vec.extend(get_differences(working_vec));
         // Break if done
-        if all_zero(&next_vec) {
+        if all_zero(next_vec) {
             break final_values.into_iter().sum();
         }
         // Swap the references
-        let holding = next_vec;
-        next_vec = working_vec;
-        working_vec = holding;
+        std::mem::swap(&mut next_vec, &mut working_vec);
     }
 }
 
@@ -49,40 +47,47 @@ fn compute_extrapolated_begin(input: Vec<i64>) -> i64 {
     loop {
         final_values.push(*working_vec.first().unwrap());
         next_vec.clear();
-        next_vec.extend(get_differences(&working_vec));
-        if all_zero(&next_vec) {
+        next_vec.extend(get_differences(working_vec));
+        if all_zero(next_vec) {
             break final_values
                 .into_iter()
                 .rev()
                 .reduce(|accum, elem| elem - accum)
                 .unwrap();
         }
-        let holding = next_vec;
-        next_vec = working_vec;
-        working_vec = holding;
+        std::mem::swap(&mut next_vec, &mut working_vec);
     }
 }
 
 impl Problem for Solution {
-    fn solve_a(&self, input: &str) -> Option<String> {
-        let lines = parse_input(input);
-        Some(
-            lines
-                .into_iter()
-                .map(compute_extrapolated_end)
-                .sum::<i64>()
-                .to_string(),
-        )
+    type Parsed = Vec<Vec<i64>>;
+
+    fn parse(&self, input: &str) -> Result<Self::Parsed, SolverError> {
+        let lines = parse_input(input)?;
+        if let Some(row) = lines.iter().position(Vec::is_empty) {
+            return Err(SolverError::InvalidInput(format!(
+                "line {} has no values",
+                row + 1
+            )));
+        }
+        Ok(lines)
     }
 
-    fn solve_b(&self, input: &str) -> Option<String> {
-        let lines = parse_input(input);
-        Some(
-            lines
-                .into_iter()
-                .map(compute_extrapolated_begin)
-                .sum::<i64>()
-                .to_string(),
-        )
+    fn solve_a(&self, lines: &Self::Parsed, _ctx: &Context) -> SolverResult {
+        Ok(lines
+            .iter()
+            .cloned()
+            .map(compute_extrapolated_end)
+            .sum::<i64>()
+            .into())
+    }
+
+    fn solve_b(&self, lines: &Self::Parsed, _ctx: &Context) -> SolverResult {
+        Ok(lines
+            .iter()
+            .cloned()
+            .map(compute_extrapolated_begin)
+            .sum::<i64>()
+            .into())
     }
 }
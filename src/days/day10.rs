@@ -1,121 +1,91 @@
-use crate::Problem;
-use aoc_parse::{parser, prelude::*};
+use crate::common::geom::Direction::{self, *};
+use crate::common::grid::{Grid, Pos};
+use crate::{Context, Problem, SolverError, SolverResult};
 use itertools::Itertools;
 pub struct Solution {}
 
-type Grid = Vec<Vec<char>>;
-type Mask = Vec<Vec<bool>>;
+type Mask = Grid<bool>;
 
-fn parse_input(input: &str) -> Grid {
-    parser!(lines(any_char+)).parse(input).unwrap()
+fn parse_input(input: &str) -> Result<Grid<char>, SolverError> {
+    Grid::parse(input, |c| c)
 }
 
-fn compute_bounds(grid: &Grid) -> (usize, usize) {
-    (grid.len(), grid[0].len())
-}
-
-fn get_deltas(c: &char) -> [Delta; 2] {
-    let mut c = c.clone();
+// The two directions a pipe connects, or None if it is not a pipe
+fn get_connections(c: &char) -> Option<[Direction; 2]> {
+    let mut c = *c;
     if c == 'S' {
         // Hard-coded!
         c = '7'
     }
     match c {
-        '|' => [Delta(-1, 0), Delta(1, 0)],
-        '-' => [Delta(0, -1), Delta(0, 1)],
-        'L' => [Delta(-1, 0), Delta(0, 1)],
-        'J' => [Delta(-1, 0), Delta(0, -1)],
-        '7' => [Delta(1, 0), Delta(0, -1)],
-        'F' => [Delta(1, 0), Delta(0, 1)],
-        _ => panic!("Bad pipe character"),
-    }
-}
-
-#[derive(PartialEq, Clone, Copy)]
-struct Position(usize, usize);
-#[derive(PartialEq, Clone, Copy)]
-struct Delta(isize, isize);
-#[derive(Debug)]
-struct OutOfBoundsError;
-
-impl Position {
-    fn add(&self, delta: Delta, bounds: (usize, usize)) -> Result<Position, OutOfBoundsError> {
-        let new = (self.0 as isize + delta.0, self.1 as isize + delta.1);
-        if (new.0 < 0)
-            || (new.0 >= bounds.0 as isize)
-            || (new.1 < 0)
-            || (new.1 >= bounds.1 as isize)
-        {
-            Err(OutOfBoundsError)
-        } else {
-            Ok(Position(new.0 as usize, new.1 as usize))
-        }
+        '|' => Some([North, South]),
+        '-' => Some([West, East]),
+        'L' => Some([North, East]),
+        'J' => Some([North, West]),
+        '7' => Some([South, West]),
+        'F' => Some([South, East]),
+        _ => None,
     }
 }
 
-fn find_s(grid: &Grid) -> Position {
-    let (rows, cols) = compute_bounds(grid);
-    for i in 0..rows {
-        for j in 0..cols {
-            if grid[i][j] == 'S' {
-                return Position(i, j);
-            }
-        }
-    }
-    panic!("Couldn't find S");
+fn find_s(grid: &Grid<char>) -> Result<Pos, SolverError> {
+    grid.find(|&c| c == 'S')
+        .ok_or(SolverError::InvalidInput("Couldn't find S".to_string()))
 }
 
 struct Walker {
-    current: Position,
-    previous: Position,
-    bounds: (usize, usize),
+    current: Pos,
+    previous: Pos,
 }
 
 impl Walker {
-    fn init(pos: Position, bounds: (usize, usize)) -> Self {
+    fn init(pos: Pos) -> Self {
         Self {
             current: pos,
             previous: pos,
-            bounds,
         }
     }
 
-    fn follow_pipe(&mut self, c: &char) {
-        let deltas = get_deltas(c);
+    fn follow_pipe(&mut self, grid: &Grid<char>) -> Result<(), SolverError> {
+        let pos = self.current;
+        let invalid = |problem: &str| SolverError::InvalidInput(format!("{problem} at {pos:?}"));
+        let connections = get_connections(&grid[pos]).ok_or_else(|| invalid("loop is broken"))?;
         // Should usually be exactly one position after filter unless self.current == self.previous
-        let new_position = deltas
-            .into_iter()
-            .map(|d| self.current.add(d, self.bounds).unwrap())
-            .filter(|new_pos| new_pos != &self.previous)
-            .next()
-            .unwrap();
-        let current_store = self.current;
-        self.current = new_position;
-        self.previous = current_store;
+        for direction in connections {
+            let new_position = grid
+                .step(pos, direction)
+                .ok_or_else(|| invalid("pipe leads off the grid"))?;
+            if new_position != self.previous {
+                self.current = new_position;
+                self.previous = pos;
+                return Ok(());
+            }
+        }
+        Err(invalid("pipe leads back on itself"))
     }
 }
 
-fn build_loop_mask(grid: &Grid) -> Mask {
-    let bounds = compute_bounds(grid);
-    let mut loop_mask = vec![vec![false; bounds.1]; bounds.0];
-    let s_pos = find_s(&grid);
-    let mut walker = Walker::init(s_pos, bounds);
+fn build_loop_mask(grid: &Grid<char>) -> Result<Mask, SolverError> {
+    let mut loop_mask = Grid::new(grid.width(), grid.height(), false);
+    let s_pos = find_s(grid)?;
+    let mut walker = Walker::init(s_pos);
     loop {
-        let pipe = grid[walker.current.0][walker.current.1];
-        walker.follow_pipe(&pipe);
-        loop_mask[walker.current.0][walker.current.1] = true;
+        walker.follow_pipe(grid)?;
+        loop_mask[walker.current] = true;
         if walker.current == s_pos {
             break;
         }
     }
-    loop_mask
+    Ok(loop_mask)
 }
 
-fn n_crosses_above(pos: &Position, grid: &Grid, loop_mask: &Mask) -> usize {
-    let horizontal_connections = (0..(pos.0))
-        .filter(|&i| loop_mask[i][pos.1])
-        .flat_map(|i| get_deltas(&grid[i][pos.1]))
-        .filter(|d| d.0 == 0);
+fn n_crosses_above(pos: Pos, grid: &Grid<char>, loop_mask: &Mask) -> usize {
+    let (row, column) = pos;
+    let horizontal_connections = (0..row)
+        .filter(|&i| loop_mask[(i, column)])
+        .flat_map(|i| get_connections(&grid[(i, column)]))
+        .flatten()
+        .filter(Direction::is_horizontal);
     // Follow the path from pos to the top of the grid
     // How many times do you have to cross the loop?
     // Loop appears in chunks starting and ending at a horizontal connection
@@ -135,45 +105,41 @@ fn n_crosses_above(pos: &Position, grid: &Grid, loop_mask: &Mask) -> usize {
         .count()
 }
 
-fn iter_grid_positions(bounds: (usize, usize)) -> impl Iterator<Item = Position> {
-    (0..(bounds.0)).flat_map(move |i| (0..(bounds.1)).map(move |j| Position(i, j)))
-}
-
 impl Problem for Solution {
-    #[allow(unused_variables)]
-    fn solve_a(&self, input: &str) -> Option<String> {
-        let grid = parse_input(input);
-        let bounds = compute_bounds(&grid);
-        let s_pos = find_s(&grid);
-        let mut walker = Walker::init(s_pos, bounds);
+    type Parsed = Grid<char>;
+
+    fn parse(&self, input: &str) -> Result<Self::Parsed, SolverError> {
+        parse_input(input)
+    }
+
+    fn solve_a(&self, grid: &Self::Parsed, _ctx: &Context) -> SolverResult {
+        let s_pos = find_s(grid)?;
+        let mut walker = Walker::init(s_pos);
         let mut n_steps = 0u64;
         loop {
-            let pipe = grid[walker.current.0][walker.current.1];
-            walker.follow_pipe(&pipe);
+            walker.follow_pipe(grid)?;
             n_steps += 1;
             if walker.current == s_pos {
                 break;
             }
         }
         let furthest = n_steps.div_euclid(2);
-        Some(furthest.to_string())
+        Ok(furthest.into())
     }
 
-    #[allow(unused_variables)]
-    fn solve_b(&self, input: &str) -> Option<String> {
-        let grid = parse_input(input);
-        let bounds = compute_bounds(&grid);
-        let loop_mask = build_loop_mask(&grid);
+    fn solve_b(&self, grid: &Self::Parsed, _ctx: &Context) -> SolverResult {
+        let loop_mask = build_loop_mask(grid)?;
 
-        let not_on_loop = |pos: &Position| !loop_mask[pos.0][pos.1];
+        let not_on_loop = |pos: &Pos| !loop_mask[*pos];
         let odd_crosses_above =
-            |pos: &Position| n_crosses_above(pos, &grid, &loop_mask).rem_euclid(2) == 1;
+            |pos: &Pos| n_crosses_above(*pos, grid, &loop_mask).rem_euclid(2) == 1;
 
-        let n_inside = iter_grid_positions(bounds)
+        let n_inside = grid
+            .positions()
             .filter(not_on_loop)
             .filter(odd_crosses_above)
             .count();
 
-        Some(n_inside.to_string())
+        Ok(n_inside.into())
     }
 }
@@ -1,29 +1,16 @@
 use crate::common::io::AocRunError;
-use crate::common::problem::Problem;
+use crate::common::problem::DynProblem;
+use crate::common::puzzle::Puzzle;
 
-mod day01;
-mod day02;
-mod day03;
-mod day04;
-mod day05;
-mod day06;
-mod day07;
-mod day08;
-mod day09;
-mod day10;
+// Declares a module for every src/days/dayNN.rs and src/days/<year>/dayNN.rs,
+// along with DAYS and build_problem, see build.rs
+include!(concat!(env!("OUT_DIR"), "/days.rs"));
 
-pub fn get_problem(problem: u32) -> Result<Box<dyn Problem>, AocRunError> {
-    match problem {
-        01 => Ok(Box::new(day01::Solution {})),
-        02 => Ok(Box::new(day02::Solution {})),
-        03 => Ok(Box::new(day03::Solution {})),
-        04 => Ok(Box::new(day04::Solution {})),
-        05 => Ok(Box::new(day05::Solution {})),
-        06 => Ok(Box::new(day06::Solution {})),
-        07 => Ok(Box::new(day07::Solution {})),
-        08 => Ok(Box::new(day08::Solution {})),
-        09 => Ok(Box::new(day09::Solution {})),
-        10 => Ok(Box::new(day10::Solution {})),
-        _ => Err(AocRunError::UnregistedProblem(problem)),
-    }
+pub fn get_problem(puzzle: Puzzle) -> Result<Box<dyn DynProblem>, AocRunError> {
+    build_problem(puzzle).ok_or(AocRunError::UnregistedProblem(puzzle))
+}
+
+// Every day with a solution file, in order of year and then day
+pub fn registered_days() -> Vec<Puzzle> {
+    DAYS.to_vec()
 }
@@ -1,30 +1,392 @@
 mod common;
 mod days;
 
-use common::{io::RunCode, problem::Problem};
-use std::{env, str::FromStr};
+use colored::Colorize;
+use common::{
+    answers::AnswerStore,
+    bench, check,
+    config::{self, Config},
+    context::Context,
+    examples,
+    fetch::{self, Fetched},
+    io::{self, AocRunError, InputKind, Part, RunCode},
+    output::{self, Format},
+    problem::{Problem, SolverError, SolverResult},
+    scaffold, submit, watch,
+};
+use rayon::prelude::*;
+use std::{
+    env, process,
+    time::{Duration, Instant},
+};
 
-pub fn main() {
-    let args: Vec<_> = env::args().collect();
-    assert!(args.len() <= 2);
-
-    if args.len() == 1 || args[1] == "all" {
-        for i in 1.. {
-            let res = RunCode::init_all(i as u32).run();
-            match res {
-                Err(common::io::AocRunError::UnregistedProblem(_)) => break,
-                Ok(rc) => {
-                    println!("Day {}", i);
-                    rc.print()
-                }
-                Err(e) => println!("{}", e),
+const USAGE: &str = "\
+Usage: cargo run -- [RUNCODE...] [--only-unsolved] [--parallel] [--timeout <secs>] [--format text|json|csv]
+       cargo run -- bench [RUNCODE...] [--runs <n>]
+       cargo run -- check [RUNCODE...] [--record]
+       cargo run -- new [<year>:]<day> [--from-example <file>]
+       cargo run -- fetch [<year>:]<day>...
+       cargo run -- submit [<year>:]<day><a|b> [--force]
+       cargo run -- watch RUNCODE [--timeout <secs>]
+       cargo run -- config
+
+Each RUNCODE is a comma separated list of items, each of which is one of
+  N[a|b|ab]    day N, optionally only part a or b
+  N-M[a|b|ab]  days N through M inclusive
+  all          every registered day of every year
+Days are in the default year unless prefixed by another year, as in 2022:5b, 2022:1-5 or 2022:all,
+and any item can be prefixed by ~ (or suffixed, for all~) to run on the examples instead.
+A single labelled example is picked with ~N[a|b|ab]:LABEL, e.g. ~8b:2 for data/examples/08-2.txt.
+For example: cargo run 1-5 ~7b 9a,10";
+
+fn usage_error(message: &str) -> ! {
+    eprintln!("{message}\n\n{USAGE}");
+    process::exit(2)
+}
+
+fn parse_run_codes(codes: &[&String]) -> Vec<RunCode> {
+    if codes.is_empty() {
+        return RunCode::parse_list("all").expect("Valid run code");
+    }
+    codes
+        .iter()
+        .flat_map(|code| {
+            RunCode::parse_list(code)
+                .unwrap_or_else(|e| usage_error(&format!("Invalid run code {code}: {e}")))
+        })
+        .collect()
+}
+
+fn parse_timeout(secs: Option<&String>) -> Option<Duration> {
+    secs.and_then(|secs| secs.parse().ok())
+        .and_then(|secs| Duration::try_from_secs_f64(secs).ok())
+        .filter(|limit| !limit.is_zero())
+        .map(Some)
+        .unwrap_or_else(|| usage_error("--timeout takes a positive number of seconds"))
+}
+
+fn run(config: &Config, args: &[String]) {
+    let mut only_unsolved = false;
+    let mut parallel = false;
+    let mut timeout = config.timeout;
+    let mut format = config.format;
+    let mut codes = vec![];
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--only-unsolved" => only_unsolved = true,
+            "--parallel" => parallel = true,
+            "--timeout" => timeout = parse_timeout(args.next()),
+            "--format" => {
+                format = args
+                    .next()
+                    .map(|f| f.parse().unwrap_or_else(|e: String| usage_error(&e)))
+                    .unwrap_or_else(|| usage_error("--format takes one of text, json or csv"))
             }
+            flag if flag.starts_with("--") => usage_error(&format!("Unknown option {flag}")),
+            _ => codes.push(arg),
         }
+    }
+    let run_codes: Vec<_> = parse_run_codes(&codes)
+        .into_iter()
+        .map(|rc| rc.with_timeout(timeout))
+        .collect();
+    let show_headers = run_codes.len() > 1;
+    if format != Format::Text {
+        colored::control::set_override(false);
+    }
+
+    // In parallel mode all results are collected before printing, so that they stay in order
+    let start = Instant::now();
+    let results: Box<dyn Iterator<Item = (RunCode, Result<(), AocRunError>)>> = if parallel {
+        let collected: Vec<_> = run_codes
+            .into_par_iter()
+            .map(|mut rc| {
+                let res = rc.run_parallel();
+                (rc, res)
+            })
+            .collect();
+        Box::new(collected.into_iter())
     } else {
-        let res = RunCode::from_str(&args[1]).expect("Valid run code").run();
+        Box::new(run_codes.into_iter().map(|mut rc| {
+            let res = rc.run();
+            (rc, res)
+        }))
+    };
+
+    let mut records = vec![];
+    let mut solve_time = Duration::ZERO;
+    for (rc, res) in results {
+        solve_time += rc.solve_time();
+        if only_unsolved && res.is_ok() && rc.is_solved() {
+            continue;
+        }
+        records.extend(rc.records(res.as_ref().err()));
+        if format != Format::Text {
+            continue;
+        }
         match res {
-            Ok(rc) => rc.print(),
+            Ok(()) => {
+                if show_headers {
+                    rc.print_header();
+                }
+                rc.print()
+            }
             Err(e) => println!("{}", e),
         }
     }
+    if format != Format::Text {
+        output::print_records(format, &records);
+        return;
+    }
+    if show_headers {
+        output::print_summary(&records);
+        println!("{}", config.summary().dimmed());
+    }
+    if parallel {
+        let wall_time = start.elapsed();
+        println!(
+            "Wall clock {:.2?} vs {:.2?} summed over parts ({:.1}x speed-up)",
+            wall_time,
+            solve_time,
+            solve_time.as_secs_f64() / wall_time.as_secs_f64()
+        );
+    }
+}
+
+fn bench(args: &[String]) {
+    let mut runs = bench::DEFAULT_RUNS;
+    let mut codes = vec![];
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--runs" => {
+                runs = args
+                    .next()
+                    .and_then(|n| n.parse().ok())
+                    .filter(|&n| n > 0)
+                    .unwrap_or_else(|| usage_error("--runs takes a positive integer"))
+            }
+            flag if flag.starts_with("--") => usage_error(&format!("Unknown option {flag}")),
+            _ => codes.push(arg),
+        }
+    }
+    let run_codes = parse_run_codes(&codes);
+    let show_headers = run_codes.len() > 1;
+
+    let mut total = Duration::ZERO;
+    for rc in run_codes {
+        match rc.bench(runs) {
+            Ok(report) => {
+                if show_headers {
+                    rc.print_header();
+                }
+                report.print();
+                total += report.total_mean();
+            }
+            Err(e) => println!("{}", e),
+        }
+    }
+    bench::print_total(total);
+}
+
+fn check(args: &[String]) {
+    let mut record = false;
+    let mut codes = vec![];
+    for arg in args {
+        match arg.as_str() {
+            "--record" => record = true,
+            flag if flag.starts_with("--") => usage_error(&format!("Unknown option {flag}")),
+            _ => codes.push(arg),
+        }
+    }
+    // Without run codes every registered day is checked, on its real input and every example
+    let run_codes: Vec<_> = if codes.is_empty() {
+        days::registered_days()
+            .into_iter()
+            .flat_map(|i| {
+                let examples = examples::list(i);
+                std::iter::once(RunCode::init_all(i)).chain(
+                    examples
+                        .into_iter()
+                        .map(move |example| RunCode::init_example(i, &example)),
+                )
+            })
+            .collect()
+    } else {
+        parse_run_codes(&codes)
+    };
+
+    let mut all_pass = true;
+    let mut store: Option<AnswerStore> = None;
+    for rc in run_codes {
+        // Each day's answers are loaded once for its run codes, and saved before moving on
+        if store.as_ref().is_none_or(|s| s.problem() != rc.problem()) {
+            if let Some(previous) = store.take().filter(|_| record) {
+                previous.save().expect("Could not write answers file");
+            }
+            store = Some(AnswerStore::load(rc.problem()).expect("Valid answers file"));
+        }
+        all_pass &= check::check(rc, store.as_mut().unwrap(), record);
+    }
+    if let Some(last) = store.filter(|_| record) {
+        last.save().expect("Could not write answers file");
+    }
+    if !all_pass {
+        process::exit(1);
+    }
+}
+
+fn new(args: &[String]) {
+    let mut problem = None;
+    let mut from_example = None;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--from-example" => {
+                from_example = Some(
+                    args.next()
+                        .unwrap_or_else(|| usage_error("--from-example takes a file")),
+                )
+            }
+            flag if flag.starts_with("--") => usage_error(&format!("Unknown option {flag}")),
+            day => problem = Some(day.parse().unwrap_or_else(|e: String| usage_error(&e))),
+        }
+    }
+    let problem = problem.unwrap_or_else(|| usage_error("new takes a day"));
+    match scaffold::new_day(problem, from_example.map(String::as_str)) {
+        Ok(created) => {
+            for path in created {
+                println!("Created {path}");
+            }
+        }
+        Err(e) => {
+            eprintln!("Could not create day {problem}: {e}");
+            process::exit(1);
+        }
+    }
+}
+
+fn fetch(args: &[String]) {
+    if args.is_empty() {
+        usage_error("fetch takes at least one day");
+    }
+    let mut all_fetched = true;
+    for day in args {
+        let problem = day.parse().unwrap_or_else(|e: String| usage_error(&e));
+        match fetch::fetch_input(problem) {
+            Ok(Fetched::Downloaded(path)) => println!("Downloaded {path}"),
+            Ok(Fetched::Cached(path)) => println!("Already have {path}"),
+            Err(e) => {
+                println!("{}", AocRunError::Fetch { problem, error: e });
+                all_fetched = false;
+            }
+        }
+    }
+    if !all_fetched {
+        process::exit(1);
+    }
+}
+
+fn submit(args: &[String]) {
+    let force = args.iter().any(|arg| arg == "--force");
+    let codes: Vec<_> = args.iter().filter(|arg| *arg != "--force").collect();
+    let [code] = codes[..] else {
+        usage_error("submit takes a single day and part, such as 5b")
+    };
+    let invalid = || usage_error(&format!("Invalid day and part {code}"));
+    let run_codes = RunCode::parse_list(code)
+        .unwrap_or_else(|e| usage_error(&format!("Invalid day and part {code}: {e}")));
+    // Exactly one part of one day, on the real input
+    let Ok([mut rc]) = <[RunCode; 1]>::try_from(run_codes) else {
+        invalid()
+    };
+    let part = match (rc.runs(Part::A), rc.runs(Part::B)) {
+        (true, false) => Part::A,
+        (false, true) => Part::B,
+        _ => invalid(),
+    };
+    if *rc.input() != InputKind::Real {
+        invalid()
+    }
+    let problem = rc.problem();
+    if let Err(e) = rc.run() {
+        println!("{e}");
+        process::exit(1);
+    }
+    let answer = match (rc.solution(part), rc.error(part)) {
+        (_, Some(e)) => {
+            println!("{e}");
+            process::exit(1);
+        }
+        (None, None) => {
+            println!("Day {problem} part {part} is not solved yet");
+            process::exit(1);
+        }
+        (Some(answer), None) => answer,
+    };
+    if let Some(e) = rc.bounds_error(part) {
+        println!("{e}");
+        if !force {
+            println!("Not submitting, use --force to submit anyway");
+            process::exit(1);
+        }
+    }
+    println!("Submitting {answer} for day {problem} part {part}");
+    match submit::submit(problem, part, answer) {
+        Ok(verdict) => println!("{verdict}"),
+        Err(e) => {
+            println!("{}", format!("Could not submit: {e}").bold().red());
+            process::exit(1);
+        }
+    }
+}
+
+fn watch(config: &Config, args: &[String]) {
+    let mut timeout = config.timeout;
+    let mut codes = vec![];
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--timeout" => timeout = parse_timeout(args.next()),
+            flag if flag.starts_with("--") => usage_error(&format!("Unknown option {flag}")),
+            _ => codes.push(arg),
+        }
+    }
+    let [code] = codes[..] else {
+        usage_error("watch takes a single run code, such as ~5b or 3,7-9")
+    };
+    if let Err(e) = RunCode::parse_list(code) {
+        usage_error(&format!("Invalid run code {code}: {e}"))
+    }
+    watch::watch(code, timeout)
+}
+
+pub fn main() {
+    let args: Vec<_> = env::args().skip(1).collect();
+    let config = config::init().unwrap_or_else(|e| {
+        eprintln!("{e}");
+        process::exit(2)
+    });
+    io::install_panic_hook();
+    if let Some(color) = config.color {
+        colored::control::set_override(color);
+    }
+    if let Some(threads) = config.threads {
+        rayon::ThreadPoolBuilder::new()
+            .num_threads(threads)
+            .build_global()
+            .expect("Thread pool is only configured once");
+    }
+
+    match args.first().map(String::as_str) {
+        Some("bench") => bench(&args[1..]),
+        Some("check") => check(&args[1..]),
+        Some("new") => new(&args[1..]),
+        Some("fetch") => fetch(&args[1..]),
+        Some("submit") => submit(&args[1..]),
+        Some("watch") => watch(config, &args[1..]),
+        Some("config") => config.print(),
+        _ => run(config, &args),
+    }
 }